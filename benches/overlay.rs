@@ -0,0 +1,105 @@
+//! Measures the per-frame cost of the modal overlay.
+//!
+//! Run with `cargo bench --bench overlay`. Each case renders the same application UI, made
+//! of a number of labels, with and without an open spinner above it. The difference between
+//! both is reported as the cost of the overlay itself, including tessellation.
+//!
+//! The documented budget is 50 µs per frame in release mode.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use egui_modal_spinner::ModalSpinner;
+
+/// Number of frames rendered per case when running with `cargo bench`.
+const BENCH_FRAMES: u32 = 2_000;
+/// The per-frame budget of the overlay.
+const BUDGET: Duration = Duration::from_micros(50);
+
+const SCREEN_SIZES: [(f32, f32); 3] = [(640.0, 480.0), (1920.0, 1080.0), (3840.0, 2160.0)];
+const CONTENT_LINES: [usize; 3] = [0, 10, 100];
+
+fn main() {
+    // `cargo bench` passes `--bench`. Without it, e.g. when running through `cargo test`,
+    // only a single frame per case is rendered to make sure the benchmark still works.
+    let frames = if std::env::args().any(|a| a == "--bench") {
+        BENCH_FRAMES
+    } else {
+        1
+    };
+
+    println!(
+        "{:<12} {:>8} {:>14} {:>14} {:>14}",
+        "screen", "lines", "frame", "with spinner", "overlay"
+    );
+
+    for (width, height) in SCREEN_SIZES {
+        for lines in CONTENT_LINES {
+            let screen_rect =
+                egui::Rect::from_min_size(egui::Pos2::ZERO, egui::vec2(width, height));
+
+            let base = measure(screen_rect, lines, false, frames);
+            let with_spinner = measure(screen_rect, lines, true, frames);
+            let overlay = with_spinner.saturating_sub(base);
+
+            println!(
+                "{:<12} {:>8} {:>14?} {:>14?} {:>14?}{}",
+                format!("{width}x{height}"),
+                lines,
+                base,
+                with_spinner,
+                overlay,
+                if overlay > BUDGET {
+                    "  over budget!"
+                } else {
+                    ""
+                }
+            );
+        }
+    }
+}
+
+/// Renders the given number of frames and returns the average time required per frame.
+fn measure(screen_rect: egui::Rect, lines: usize, open: bool, frames: u32) -> Duration {
+    let ctx = egui::Context::default();
+    let mut spinner = ModalSpinner::new();
+
+    if open {
+        spinner.open();
+    }
+
+    // Warm up font atlas and caches so that only the steady state is measured.
+    run_frame(&ctx, &mut spinner, screen_rect, lines);
+
+    let start = Instant::now();
+
+    for _ in 0..frames {
+        run_frame(&ctx, &mut spinner, screen_rect, lines);
+    }
+
+    start.elapsed() / frames
+}
+
+fn run_frame(
+    ctx: &egui::Context,
+    spinner: &mut ModalSpinner,
+    screen_rect: egui::Rect,
+    lines: usize,
+) {
+    let input = egui::RawInput {
+        screen_rect: Some(screen_rect),
+        ..Default::default()
+    };
+
+    let output = ctx.run(input, |ctx| {
+        egui::CentralPanel::default().show(ctx, |ui| {
+            for i in 0..lines {
+                ui.label(format!("Content line {i}"));
+            }
+        });
+
+        spinner.update(ctx);
+    });
+
+    black_box(ctx.tessellate(output.shapes, output.pixels_per_point));
+}
@@ -0,0 +1,88 @@
+//! Shows how to use the spinner with an integration other than eframe, like egui-winit with
+//! egui-wgpu or bevy_egui.
+//!
+//! The integration is simulated by a loop that runs the egui context whenever it is woken up.
+//! A real integration would run the context from its event loop or render system, pass the
+//! platform input instead of the default input and paint the returned shapes.
+
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+use egui_modal_spinner::ModalSpinner;
+
+/// Events that wake up the simulated event loop.
+enum Event {
+    Repaint,
+}
+
+fn main() {
+    let ctx = egui::Context::default();
+    let (event_tx, event_rx) = mpsc::channel();
+
+    // Wake up the event loop when the spinner or a worker thread requests a repaint.
+    // Integrations that already forward the repaint requests of the context to their event
+    // loop, like egui-winit, don't need the callback of the spinner.
+    let repaint_tx = event_tx.clone();
+    ctx.set_request_repaint_callback(move |_| {
+        let _ = repaint_tx.send(Event::Repaint);
+    });
+
+    let mut spinner = ModalSpinner::new().on_repaint_request(move || {
+        let _ = event_tx.send(Event::Repaint);
+    });
+
+    let handle = spinner.handle();
+    spinner.open();
+
+    thread::spawn(move || {
+        for i in 0..=10 {
+            // Do some heavy resource task
+            thread::sleep(Duration::from_millis(100));
+            handle.set_progress(i as f32 / 10.0);
+        }
+
+        handle.close();
+    });
+
+    let mut time = 0.0;
+
+    loop {
+        let input = egui::RawInput {
+            // The screen rect does not have to start at the origin,
+            // e.g. when rendering into a part of a game window.
+            screen_rect: Some(egui::Rect::from_min_size(
+                egui::pos2(100.0, 100.0),
+                egui::vec2(800.0, 600.0),
+            )),
+            time: Some(time),
+            ..Default::default()
+        };
+
+        let _ = ctx.run(input, |ctx| {
+            egui::CentralPanel::default().show(ctx, |ui| {
+                ui.label("Loading some data...");
+            });
+
+            spinner.update(ctx);
+        });
+
+        println!(
+            "progress: {:.0} %",
+            spinner.progress().unwrap_or_default() * 100.0
+        );
+
+        if !spinner.should_disable_ui() {
+            println!("finished");
+            break;
+        }
+
+        // Wait until a repaint is requested, like an event loop waiting for the next event.
+        match event_rx.recv_timeout(Duration::from_secs(1)) {
+            Ok(Event::Repaint) | Err(mpsc::RecvTimeoutError::Timeout) => {}
+            Err(mpsc::RecvTimeoutError::Disconnected) => break,
+        }
+
+        time += 1.0 / 60.0;
+    }
+}
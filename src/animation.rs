@@ -0,0 +1,156 @@
+//! Image sequences played in a loop as the spinner.
+
+use std::fmt;
+use std::time::Duration;
+
+use crate::indicator::SpinnerWidget;
+
+/// A sequence of images played in a loop instead of the default spinner, like a branded
+/// loading animation exported as GIF or APNG frames.
+///
+/// The animation implements [`SpinnerWidget`], so it is set using
+/// [`ModalSpinner::custom_spinner`]. Every frame is displayed for its own duration, starting
+/// with the first frame when the spinner is opened. The frames are scaled to the spinner
+/// size, keeping their aspect ratio.
+///
+/// # Example
+/// ```rust
+/// use std::time::Duration;
+///
+/// use egui_modal_spinner::{AnimatedImage, ModalSpinner};
+///
+/// fn create_spinner(ctx: &egui::Context, frames: Vec<egui::ColorImage>) -> ModalSpinner {
+///     let frames = frames
+///         .into_iter()
+///         .map(|image| (image, Duration::from_millis(40)));
+///
+///     ModalSpinner::new()
+///         .spinner_size(64.0)
+///         .custom_spinner(AnimatedImage::from_images(ctx, "loading", frames))
+/// }
+/// ```
+///
+/// [`ModalSpinner::custom_spinner`]: crate::ModalSpinner::custom_spinner
+#[derive(Clone)]
+pub struct AnimatedImage {
+    frames: Vec<(egui::TextureHandle, Duration)>,
+}
+
+impl fmt::Debug for AnimatedImage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AnimatedImage")
+            .field("frames", &self.frames.len())
+            .field("loop_duration", &self.loop_duration())
+            .finish()
+    }
+}
+
+/// Creation methods
+impl AnimatedImage {
+    /// Creates an animation from textures, each displayed for the given duration.
+    /// Frames with a duration of zero are skipped.
+    pub fn new(frames: impl IntoIterator<Item = (egui::TextureHandle, Duration)>) -> Self {
+        Self {
+            frames: frames
+                .into_iter()
+                .filter(|(_, duration)| !duration.is_zero())
+                .collect(),
+        }
+    }
+
+    /// Creates an animation from textures that are all displayed for the same duration.
+    pub fn from_textures(
+        textures: impl IntoIterator<Item = egui::TextureHandle>,
+        frame_duration: Duration,
+    ) -> Self {
+        Self::new(
+            textures
+                .into_iter()
+                .map(|texture| (texture, frame_duration)),
+        )
+    }
+
+    /// Creates an animation from raw images with their durations, e.g. the decoded frames of
+    /// a GIF. The images are loaded into textures named after the given name.
+    /// Frames with a duration of zero are skipped.
+    pub fn from_images(
+        ctx: &egui::Context,
+        name: &str,
+        frames: impl IntoIterator<Item = (egui::ColorImage, Duration)>,
+    ) -> Self {
+        Self::new(
+            frames
+                .into_iter()
+                .filter(|(_, duration)| !duration.is_zero())
+                .enumerate()
+                .map(|(i, (image, duration))| {
+                    let texture = ctx.load_texture(
+                        format!("{name}_{i}"),
+                        image,
+                        egui::TextureOptions::LINEAR,
+                    );
+
+                    (texture, duration)
+                }),
+        )
+    }
+}
+
+/// Getter and setter
+impl AnimatedImage {
+    /// Gets the number of frames of the animation.
+    pub const fn frame_count(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Gets the duration of a single loop of the animation.
+    /// The duration saturates instead of overflowing for very long frame durations.
+    pub fn loop_duration(&self) -> Duration {
+        self.frames
+            .iter()
+            .map(|(_, duration)| *duration)
+            .fold(Duration::ZERO, Duration::saturating_add)
+    }
+
+    /// Gets the index of the frame displayed at the given time since the animation started,
+    /// and the time until the next frame is displayed.
+    /// Returns `None` if the animation has no frames.
+    pub fn frame_at(&self, elapsed: Duration) -> Option<(usize, Duration)> {
+        let loop_duration = self.loop_duration().as_nanos();
+
+        if loop_duration == 0 {
+            return None;
+        }
+
+        let mut time = elapsed.as_nanos() % loop_duration;
+
+        for (i, (_, duration)) in self.frames.iter().enumerate() {
+            let duration_nanos = duration.as_nanos();
+
+            if time < duration_nanos {
+                let remaining =
+                    Duration::from_nanos(u64::try_from(duration_nanos - time).unwrap_or(u64::MAX));
+                return Some((i, remaining));
+            }
+
+            time -= duration_nanos;
+        }
+
+        None
+    }
+}
+
+impl SpinnerWidget for AnimatedImage {
+    fn ui(&self, ui: &mut egui::Ui, elapsed: Duration, _progress: Option<f32>) {
+        let Some((index, remaining)) = self.frame_at(elapsed) else {
+            return;
+        };
+
+        let Some((texture, _)) = self.frames.get(index) else {
+            return;
+        };
+
+        ui.add(egui::Image::new(texture).fit_to_exact_size(ui.available_size()));
+        ui.ctx().request_repaint_after(remaining);
+    }
+}
@@ -0,0 +1,147 @@
+//! The mini-map displayed below the progress bar for batch operations with many items.
+
+use crate::style::OverlayStyle;
+
+/// The maximum number of cells displayed. Larger batches are displayed with every cell
+/// representing a range of items, so the number of painted rects stays bounded.
+const MAX_CELLS: usize = 1024;
+/// The maximum size of a cell in points, including the gap to the next cell.
+const MAX_CELL_PITCH: f32 = 12.0;
+/// The gap between two cells in relation to the cell pitch.
+const CELL_GAP: f32 = 0.2;
+
+/// The state of an item of a batch operation, see [`ModalSpinner::set_item_count`].
+///
+/// [`ModalSpinner::set_item_count`]: crate::ModalSpinner::set_item_count
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ItemState {
+    /// The item was not processed yet.
+    #[default]
+    Pending,
+    /// The item was processed successfully.
+    Completed,
+    /// Processing the item failed.
+    Failed,
+}
+
+/// An item of a batch operation that failed, see [`ModalSpinner::failures`].
+///
+/// [`ModalSpinner::failures`]: crate::ModalSpinner::failures
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ItemFailure {
+    /// The index of the item.
+    pub index: usize,
+    /// The reason the item failed, if reported.
+    pub reason: Option<String>,
+}
+
+impl ItemFailure {
+    /// Gets the text displayed in the list of failures.
+    pub(crate) fn label(&self) -> String {
+        self.reason.as_ref().map_or_else(
+            || format!("Item {}", self.index),
+            |reason| format!("Item {}: {reason}", self.index),
+        )
+    }
+}
+
+/// An update of an item reported through a handle.
+#[derive(Debug, Clone)]
+pub struct ItemUpdate {
+    pub index: usize,
+    pub state: ItemState,
+    /// The reason of the failure, if the item failed.
+    pub reason: Option<String>,
+}
+
+/// The layout of the cells of the mini-map.
+struct Layout {
+    cells: usize,
+    columns: usize,
+    rows: usize,
+    pitch: f32,
+}
+
+impl Layout {
+    /// Arranges the cells for the given number of items in a grid that is as wide as the
+    /// progress bar and at most a quarter as high.
+    /// Returns `None` if there are no items.
+    fn new(style: &OverlayStyle, items: usize) -> Option<Self> {
+        if items == 0 {
+            return None;
+        }
+
+        let cells = items.min(MAX_CELLS);
+        let width = style.progress_bar_width();
+
+        #[allow(clippy::cast_precision_loss)]
+        let pitch = (width * width / 4.0 / cells as f32)
+            .sqrt()
+            .min(MAX_CELL_PITCH);
+
+        #[allow(
+            clippy::cast_possible_truncation,
+            clippy::cast_sign_loss,
+            clippy::cast_precision_loss
+        )]
+        let columns = ((width / pitch).floor() as usize).clamp(1, cells);
+
+        Some(Self {
+            cells,
+            columns,
+            rows: cells.div_ceil(columns),
+            pitch,
+        })
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn size(&self) -> egui::Vec2 {
+        egui::vec2(self.columns as f32, self.rows as f32) * self.pitch
+    }
+}
+
+/// Gets the height of the mini-map for the given number of items.
+/// Returns `None` if there are no items.
+pub fn batch_map_height(style: &OverlayStyle, items: usize) -> Option<f32> {
+    Layout::new(style, items).map(|layout| layout.size().y)
+}
+
+/// Adds the mini-map with a cell for every item, colored by the state of the item.
+///
+/// If there are more items than cells, a cell is displayed as failed if one of its items
+/// failed and as completed if all of its items were completed.
+pub fn ui_batch_map(ui: &mut egui::Ui, style: &OverlayStyle, items: &[ItemState]) {
+    let Some(layout) = Layout::new(style, items.len()) else {
+        return;
+    };
+
+    let (rect, _) = ui.allocate_exact_size(layout.size(), egui::Sense::hover());
+
+    let mut mesh = egui::Mesh::default();
+    let size = egui::Vec2::splat(layout.pitch * (1.0 - CELL_GAP));
+
+    for cell in 0..layout.cells {
+        let start = cell * items.len() / layout.cells;
+        let end = (cell + 1) * items.len() / layout.cells;
+        let cell_items = items.get(start..end).unwrap_or_default();
+
+        let color = if cell_items.contains(&ItemState::Failed) {
+            ui.visuals().error_fg_color
+        } else if cell_items.iter().all(|item| *item == ItemState::Completed) {
+            style.spinner_color(ui.visuals())
+        } else {
+            ui.visuals().widgets.inactive.bg_fill
+        };
+
+        #[allow(clippy::cast_precision_loss)]
+        let offset = egui::vec2(
+            (cell % layout.columns) as f32,
+            (cell / layout.columns) as f32,
+        ) * layout.pitch;
+
+        mesh.add_colored_rect(egui::Rect::from_min_size(rect.min + offset, size), color);
+    }
+
+    ui.painter().add(mesh);
+}
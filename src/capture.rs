@@ -0,0 +1,99 @@
+//! Capturing the last rendered frame to display it frozen beneath the modal.
+
+use std::fmt;
+
+/// The name of the texture the captured frame is loaded into.
+const TEXTURE_NAME: &str = "egui_modal_spinner_frame_capture";
+
+/// Requests a screenshot of the application when the spinner opens
+/// and keeps the captured frame as a texture while the spinner is open.
+#[derive(Clone, Default)]
+pub struct FrameCapture {
+    /// The user data of the requested screenshot. None if no screenshot is pending.
+    request: Option<egui::UserData>,
+    /// The captured frame and the screen rect at the time it was captured.
+    frame: Option<(egui::TextureHandle, egui::Rect)>,
+}
+
+impl fmt::Debug for FrameCapture {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FrameCapture")
+            .field("pending", &self.request.is_some())
+            .field("captured", &self.frame.is_some())
+            .finish()
+    }
+}
+
+impl FrameCapture {
+    /// Requests a screenshot of the frame that is currently rendered.
+    /// This discards the previously captured frame.
+    pub fn request(&mut self, ctx: &egui::Context) {
+        let user_data = egui::UserData::new(TEXTURE_NAME);
+
+        ctx.send_viewport_cmd(egui::ViewportCommand::Screenshot(user_data.clone()));
+
+        self.request = Some(user_data);
+        self.frame = None;
+    }
+
+    /// Loads the requested screenshot into a texture once the integration returned it.
+    /// If `desaturate` is true, the screenshot is converted to grayscale before it is loaded.
+    /// The captured frame is discarded when the size of the screen changes.
+    pub fn update(&mut self, ctx: &egui::Context, desaturate: bool) {
+        let screen_rect = ctx.screen_rect();
+
+        if let Some(request) = &self.request {
+            let image = ctx.input(|i| {
+                i.events.iter().find_map(|event| match event {
+                    egui::Event::Screenshot {
+                        user_data, image, ..
+                    } if user_data == request => Some(image.clone()),
+                    _ => None,
+                })
+            });
+
+            if let Some(mut image) = image {
+                if desaturate {
+                    desaturate_image(std::sync::Arc::make_mut(&mut image));
+                }
+
+                let texture = ctx.load_texture(TEXTURE_NAME, image, egui::TextureOptions::LINEAR);
+                self.frame = Some((texture, screen_rect));
+                self.request = None;
+            }
+        }
+
+        if self
+            .frame
+            .as_ref()
+            .is_some_and(|(_, rect)| *rect != screen_rect)
+        {
+            self.frame = None;
+        }
+    }
+
+    /// Gets the texture of the captured frame, if available.
+    pub fn texture(&self) -> Option<&egui::TextureHandle> {
+        self.frame.as_ref().map(|(texture, _)| texture)
+    }
+
+    /// Discards the captured frame and any pending request.
+    pub fn clear(&mut self) {
+        self.request = None;
+        self.frame = None;
+    }
+}
+
+/// Converts the pixels of the image to grayscale, keeping their alpha.
+/// Uses the Rec. 601 luma weights, which work well enough on the gamma encoded pixels.
+fn desaturate_image(image: &mut egui::ColorImage) {
+    for pixel in &mut image.pixels {
+        let [r, g, b, a] = pixel.to_array();
+        let luma = (u32::from(r) * 77 + u32::from(g) * 150 + u32::from(b) * 29) >> 8;
+
+        #[allow(clippy::cast_possible_truncation)]
+        let luma = luma as u8;
+
+        *pixel = egui::Color32::from_rgba_premultiplied(luma, luma, luma, a);
+    }
+}
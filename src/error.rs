@@ -0,0 +1,29 @@
+use std::fmt;
+
+/// Error returned by the `try_` configuration methods of the spinner
+/// when an invalid value is passed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConfigError {
+    /// The given size is not a finite number greater than zero.
+    InvalidSize(f32),
+    /// The given frame rate is not a finite number greater than zero, or its frame time
+    /// can not be represented as a duration.
+    InvalidFrameRate(f32),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidSize(size) => write!(
+                f,
+                "invalid size {size}, expected a finite number greater than zero"
+            ),
+            Self::InvalidFrameRate(fps) => write!(
+                f,
+                "invalid frame rate {fps}, expected a finite number greater than zero"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
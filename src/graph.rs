@@ -0,0 +1,191 @@
+//! Tasks consisting of multiple steps that depend on each other.
+
+use crate::steps::StepState;
+
+/// Identifies a task added to a [`TaskGraph`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TaskId(usize);
+
+/// A single task of the graph.
+#[derive(Debug, Clone)]
+struct Node {
+    label: String,
+    /// The tasks that have to be completed before this task can be started.
+    dependencies: Vec<TaskId>,
+    state: StepState,
+}
+
+/// A set of tasks where tasks can depend on other tasks, like the steps of a load pipeline.
+///
+/// Unlike the sequential steps set with [`ModalSpinner::set_steps`], multiple tasks can be
+/// active at the same time. A task is ready to be started once all of its dependencies were
+/// completed. The graph does not execute the tasks itself, the application starts them, e.g.
+/// on worker threads, and reports when they are completed.
+///
+/// Attach the graph to a spinner using [`ModalSpinner::set_task_graph`], which then displays
+/// the active tasks and the overall completion of the graph.
+///
+/// # Example
+/// ```rust
+/// use egui_modal_spinner::TaskGraph;
+///
+/// let mut graph = TaskGraph::new();
+///
+/// let download = graph.add_task("Downloading", []);
+/// let fonts = graph.add_task("Loading fonts", []);
+/// let install = graph.add_task("Installing", [download, fonts]);
+///
+/// assert_eq!(graph.ready(), vec![download, fonts]);
+///
+/// graph.start(download);
+/// graph.start(fonts);
+/// graph.complete(download);
+/// graph.complete(fonts);
+///
+/// assert_eq!(graph.ready(), vec![install]);
+/// ```
+///
+/// [`ModalSpinner::set_steps`]: crate::ModalSpinner::set_steps
+/// [`ModalSpinner::set_task_graph`]: crate::ModalSpinner::set_task_graph
+#[derive(Debug, Clone, Default)]
+pub struct TaskGraph {
+    nodes: Vec<Node>,
+}
+
+/// Creation methods
+impl TaskGraph {
+    /// Creates a new graph without tasks.
+    pub const fn new() -> Self {
+        Self { nodes: Vec::new() }
+    }
+
+    /// Adds a task that can be started once the given tasks were completed.
+    ///
+    /// A task can only depend on tasks that were added before it, so the graph can not
+    /// contain cycles. Dependencies that don't belong to the graph are ignored.
+    pub fn add_task(
+        &mut self,
+        label: impl Into<String>,
+        dependencies: impl IntoIterator<Item = TaskId>,
+    ) -> TaskId {
+        let id = TaskId(self.nodes.len());
+
+        self.nodes.push(Node {
+            label: label.into(),
+            dependencies: dependencies
+                .into_iter()
+                .filter(|dependency| dependency.0 < id.0)
+                .collect(),
+            state: StepState::Pending,
+        });
+
+        id
+    }
+}
+
+/// Getter and setter
+impl TaskGraph {
+    /// Gets the label of the given task, or None if it does not belong to the graph.
+    pub fn label(&self, id: TaskId) -> Option<&str> {
+        self.nodes.get(id.0).map(|node| node.label.as_str())
+    }
+
+    /// Gets the state of the given task, or None if it does not belong to the graph.
+    pub fn state(&self, id: TaskId) -> Option<StepState> {
+        self.nodes.get(id.0).map(|node| node.state)
+    }
+
+    /// Gets the tasks that were not started yet, but whose dependencies were all completed.
+    pub fn ready(&self) -> Vec<TaskId> {
+        (0..self.nodes.len())
+            .map(TaskId)
+            .filter(|&id| self.is_ready(id))
+            .collect()
+    }
+
+    /// Gets the tasks that were started but not yet completed.
+    pub fn active(&self) -> Vec<TaskId> {
+        (0..self.nodes.len())
+            .map(TaskId)
+            .filter(|&id| self.state(id) == Some(StepState::Active))
+            .collect()
+    }
+
+    /// Gets the completed share of the tasks between 0.0 and 1.0.
+    /// A graph without tasks is considered completed.
+    pub fn progress(&self) -> f32 {
+        if self.nodes.is_empty() {
+            return 1.0;
+        }
+
+        let completed = self
+            .nodes
+            .iter()
+            .filter(|node| node.state == StepState::Completed)
+            .count();
+
+        #[allow(clippy::cast_precision_loss)]
+        let progress = completed as f32 / self.nodes.len() as f32;
+
+        progress
+    }
+
+    /// Checks if all tasks of the graph were completed.
+    pub fn is_finished(&self) -> bool {
+        self.nodes
+            .iter()
+            .all(|node| node.state == StepState::Completed)
+    }
+}
+
+/// Implementation methods
+impl TaskGraph {
+    /// Marks the given task as started.
+    ///
+    /// Returns false if the task does not belong to the graph, was already started
+    /// or if one of its dependencies was not completed yet.
+    pub fn start(&mut self, id: TaskId) -> bool {
+        if !self.is_ready(id) {
+            return false;
+        }
+
+        if let Some(node) = self.nodes.get_mut(id.0) {
+            node.state = StepState::Active;
+        }
+
+        true
+    }
+
+    /// Marks the given task as completed.
+    ///
+    /// Returns false if the task does not belong to the graph or was not started.
+    pub fn complete(&mut self, id: TaskId) -> bool {
+        match self.nodes.get_mut(id.0) {
+            Some(node) if node.state == StepState::Active => {
+                node.state = StepState::Completed;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Checks if the given task was not started yet and all of its dependencies were
+    /// completed.
+    fn is_ready(&self, id: TaskId) -> bool {
+        self.nodes.get(id.0).is_some_and(|node| {
+            node.state == StepState::Pending
+                && node
+                    .dependencies
+                    .iter()
+                    .all(|&dependency| self.state(dependency) == Some(StepState::Completed))
+        })
+    }
+
+    /// Gets the labels of the active tasks, in the order they were added.
+    pub(crate) fn active_labels(&self) -> impl Iterator<Item = &str> {
+        self.nodes
+            .iter()
+            .filter(|node| node.state == StepState::Active)
+            .map(|node| node.label.as_str())
+    }
+}
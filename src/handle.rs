@@ -0,0 +1,566 @@
+//! Thread-safe handles to control the spinner from worker threads.
+
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+
+use crate::batch::{ItemState, ItemUpdate};
+use crate::utils::Callback;
+
+/// Stored in the progress slot if no progress was reported since the last update.
+/// This is the bit pattern of a NaN, which is never stored as a progress.
+const NO_PROGRESS: u32 = u32::MAX;
+
+/// A clonable, thread-safe handle to control a [`ModalSpinner`] from worker threads.
+///
+/// The requests made through the handle are applied the next time the spinner is updated,
+/// and a repaint is requested so that this happens as soon as possible.
+///
+/// The progress, the message and the item updates are passed to the spinner without waiting
+/// for it. Only the latest progress, the latest message and the latest update of every item
+/// are kept, updates that are replaced before the spinner is updated are dropped.
+/// This way, a worker reporting in a tight loop does not pile up updates while the UI is
+/// stalled.
+/// The number of updates can be inspected using [`SpinnerHandle::stats`].
+///
+/// # Example
+/// ```rust
+/// use std::thread;
+///
+/// use egui_modal_spinner::ModalSpinner;
+///
+/// let mut spinner = ModalSpinner::new();
+/// let handle = spinner.handle();
+///
+/// spinner.open();
+///
+/// thread::spawn(move || {
+///     for i in 0..100 {
+///         // Do some heavy resource task
+///         handle.set_progress(i as f32 / 100.0);
+///     }
+///
+///     handle.set_message("Cleaning up...");
+///     handle.close();
+/// });
+/// ```
+///
+/// [`ModalSpinner`]: crate::ModalSpinner
+#[derive(Debug, Clone)]
+pub struct SpinnerHandle {
+    shared: Arc<SharedState>,
+}
+
+/// The handle owned by a spinner.
+///
+/// A cloned spinner is a separate spinner, so the clone gets a new handle
+/// instead of sharing the requests with the original.
+#[derive(Debug)]
+pub(crate) struct OwnedHandle(SpinnerHandle);
+
+impl OwnedHandle {
+    pub(crate) fn new() -> Self {
+        Self(SpinnerHandle::new())
+    }
+
+    /// Gets a handle sharing the state with the spinner.
+    pub(crate) fn share(&self) -> SpinnerHandle {
+        self.0.clone()
+    }
+}
+
+impl Clone for OwnedHandle {
+    fn clone(&self) -> Self {
+        Self(self.0.detach())
+    }
+}
+
+impl std::ops::Deref for OwnedHandle {
+    type Target = SpinnerHandle;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// The state shared between the spinner and its handles.
+#[derive(Debug)]
+struct SharedState {
+    /// If a handle requested the spinner to close.
+    close_requested: AtomicBool,
+    /// If the user requested to cancel the task using the cancel button.
+    cancel_requested: AtomicBool,
+    /// If a handle acknowledged that the task stopped after the cancel request.
+    cancel_acknowledged: AtomicBool,
+    /// Incremented every time the spinner is opened, used to identify the current opening.
+    session: AtomicU64,
+    /// The session a dropped guard requested to close, or zero if none.
+    guard_close: AtomicU64,
+    /// The bits of the last progress reported by a handle, or `NO_PROGRESS`.
+    progress: AtomicU32,
+    /// The latest message set by the handles that was not applied yet.
+    /// The inner value is None if the message was cleared.
+    #[allow(clippy::option_option)]
+    message: Mutex<Option<Option<String>>>,
+    /// The latest update of every item reported by the handles that was not applied yet.
+    items: Mutex<BTreeMap<usize, ItemUpdate>>,
+    /// The number of progress, message and item updates sent by the handles.
+    sent: AtomicU64,
+    /// The number of updates that were replaced or discarded before they were applied.
+    dropped: AtomicU64,
+    /// The number of updates applied by the spinner.
+    applied: AtomicU64,
+    /// The context the spinner is updated with, used to request repaints.
+    ctx: OnceLock<egui::Context>,
+    /// Called in addition to requesting a repaint from the context.
+    /// Set once, so that requesting a repaint never takes a lock on the worker thread.
+    repaint_callback: OnceLock<Callback<()>>,
+}
+
+impl SpinnerHandle {
+    /// Requests the spinner to close.
+    ///
+    /// Requests made while the spinner is closed are discarded with the next update.
+    pub fn close(&self) {
+        self.shared.close_requested.store(true, Ordering::Relaxed);
+        self.request_repaint();
+    }
+
+    /// Sets the progress of the task between 0.0 and 1.0.
+    ///
+    /// See [`ModalSpinner::set_progress`] for more information.
+    ///
+    /// [`ModalSpinner::set_progress`]: crate::ModalSpinner::set_progress
+    pub fn set_progress(&self, progress: f32) {
+        if progress.is_nan() {
+            return;
+        }
+
+        let previous = self
+            .shared
+            .progress
+            .swap(progress.clamp(0.0, 1.0).to_bits(), Ordering::Relaxed);
+
+        self.shared.sent.fetch_add(1, Ordering::Relaxed);
+
+        if previous != NO_PROGRESS {
+            self.shared.dropped.fetch_add(1, Ordering::Relaxed);
+        }
+
+        self.request_repaint();
+    }
+
+    /// Sets the status message displayed beneath the spinner.
+    ///
+    /// See [`ModalSpinner::set_message`] for more information.
+    ///
+    /// [`ModalSpinner::set_message`]: crate::ModalSpinner::set_message
+    pub fn set_message(&self, message: impl Into<String>) {
+        self.store_message(Some(message.into()));
+    }
+
+    /// Removes the status message displayed beneath the spinner.
+    pub fn clear_message(&self) {
+        self.store_message(None);
+    }
+
+    /// Marks the item at the given index of the batch as completed.
+    ///
+    /// See [`ModalSpinner::set_item_count`] for more information.
+    ///
+    /// [`ModalSpinner::set_item_count`]: crate::ModalSpinner::set_item_count
+    pub fn complete_item(&self, index: usize) {
+        self.store_item(index, ItemState::Completed, None);
+    }
+
+    /// Marks the item at the given index of the batch as failed.
+    ///
+    /// See [`ModalSpinner::set_item_count`] for more information.
+    ///
+    /// [`ModalSpinner::set_item_count`]: crate::ModalSpinner::set_item_count
+    pub fn fail_item(&self, index: usize) {
+        self.store_item(index, ItemState::Failed, None);
+    }
+
+    /// Marks the item at the given index of the batch as failed because of the given reason.
+    ///
+    /// See [`ModalSpinner::fail_item_with_reason`] for more information.
+    ///
+    /// [`ModalSpinner::fail_item_with_reason`]: crate::ModalSpinner::fail_item_with_reason
+    pub fn fail_item_with_reason(&self, index: usize, reason: impl Into<String>) {
+        self.store_item(index, ItemState::Failed, Some(reason.into()));
+    }
+
+    /// Checks if the user requested to cancel the task using the cancel button.
+    ///
+    /// Once the task stopped, call [`SpinnerHandle::acknowledge_cancel`].
+    /// See [`ModalSpinner::show_cancel_button`] for more information.
+    ///
+    /// [`ModalSpinner::show_cancel_button`]: crate::ModalSpinner::show_cancel_button
+    pub fn is_cancel_requested(&self) -> bool {
+        self.shared.cancel_requested.load(Ordering::Acquire)
+    }
+
+    /// Acknowledges that the task stopped after the user requested to cancel it.
+    ///
+    /// The spinner displays that the task is being cancelled until the request is
+    /// acknowledged and is then closed with the next update.
+    /// Acknowledgements without a cancel request are ignored.
+    pub fn acknowledge_cancel(&self) {
+        self.shared
+            .cancel_acknowledged
+            .store(true, Ordering::Release);
+        self.request_repaint();
+    }
+
+    /// Gets the number of updates sent through the handles of the spinner.
+    ///
+    /// The statistics are shared by all handles of the spinner and count the progress,
+    /// message and item updates since the spinner was created.
+    pub fn stats(&self) -> HandleStats {
+        HandleStats {
+            sent: self.shared.sent.load(Ordering::Relaxed),
+            dropped: self.shared.dropped.load(Ordering::Relaxed),
+            applied: self.shared.applied.load(Ordering::Relaxed),
+        }
+    }
+
+    fn store_message(&self, message: Option<String>) {
+        let Ok(mut slot) = self.shared.message.lock() else {
+            return;
+        };
+
+        self.record_sent(slot.replace(message).is_some());
+        drop(slot);
+
+        self.request_repaint();
+    }
+
+    fn store_item(&self, index: usize, state: ItemState, reason: Option<String>) {
+        let Ok(mut items) = self.shared.items.lock() else {
+            return;
+        };
+
+        let update = ItemUpdate {
+            index,
+            state,
+            reason,
+        };
+
+        self.record_sent(items.insert(index, update).is_some());
+        drop(items);
+
+        self.request_repaint();
+    }
+
+    /// Records a sent update and if it replaced an update that was not applied yet.
+    fn record_sent(&self, replaced: bool) {
+        self.shared.sent.fetch_add(1, Ordering::Relaxed);
+
+        if replaced {
+            self.shared.dropped.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Statistics about the updates sent through the handles of a spinner,
+/// see [`SpinnerHandle::stats`].
+///
+/// Updates that were sent but neither dropped nor applied are still pending and are applied
+/// with the next update of the spinner.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct HandleStats {
+    /// The number of progress, message and item updates sent.
+    pub sent: u64,
+    /// The number of updates that were replaced by a newer update before the spinner was
+    /// updated, or that were discarded because the spinner was closed.
+    pub dropped: u64,
+    /// The number of updates applied by the spinner.
+    pub applied: u64,
+}
+
+/// Methods used by the spinner
+impl SpinnerHandle {
+    pub(crate) fn new() -> Self {
+        Self {
+            shared: Arc::new(SharedState {
+                close_requested: AtomicBool::new(false),
+                cancel_requested: AtomicBool::new(false),
+                cancel_acknowledged: AtomicBool::new(false),
+                session: AtomicU64::new(0),
+                guard_close: AtomicU64::new(0),
+                progress: AtomicU32::new(NO_PROGRESS),
+                message: Mutex::new(None),
+                items: Mutex::new(BTreeMap::new()),
+                sent: AtomicU64::new(0),
+                dropped: AtomicU64::new(0),
+                applied: AtomicU64::new(0),
+                ctx: OnceLock::new(),
+                repaint_callback: OnceLock::new(),
+            }),
+        }
+    }
+
+    /// Creates a new handle that does not share any requests with this handle.
+    /// The sessions are continued and the context and the repaint callback are kept.
+    fn detach(&self) -> Self {
+        let handle = Self::new();
+
+        handle.shared.session.store(
+            self.shared.session.load(Ordering::Relaxed),
+            Ordering::Relaxed,
+        );
+
+        if let Some(ctx) = self.shared.ctx.get() {
+            handle.set_context(ctx);
+        }
+
+        if let Some(callback) = self.shared.repaint_callback.get() {
+            handle.set_repaint_callback(callback.clone());
+        }
+
+        handle
+    }
+
+    /// Requests a repaint from the context and calls the repaint callback, if set.
+    pub(crate) fn request_repaint(&self) {
+        if let Some(ctx) = self.shared.ctx.get() {
+            ctx.request_repaint();
+        }
+
+        if let Some(callback) = self.shared.repaint_callback.get() {
+            callback.call(&());
+        }
+    }
+
+    /// Sets the callback that is called when a repaint is requested from a worker thread,
+    /// if no callback was set yet.
+    pub(crate) fn set_repaint_callback(&self, callback: Callback<()>) {
+        let _ = self.shared.repaint_callback.set(callback);
+    }
+
+    /// Sets the context to request repaints from, if not already set.
+    pub(crate) fn set_context(&self, ctx: &egui::Context) {
+        let _ = self.shared.ctx.get_or_init(|| ctx.clone());
+    }
+
+    /// Records the number of updates that were applied by the spinner.
+    pub(crate) fn record_applied(&self, count: u64) {
+        self.shared.applied.fetch_add(count, Ordering::Relaxed);
+    }
+
+    /// Records the number of updates that were discarded by the spinner.
+    pub(crate) fn record_dropped(&self, count: u64) {
+        self.shared.dropped.fetch_add(count, Ordering::Relaxed);
+    }
+
+    /// Checks if a close was requested and resets the request.
+    pub(crate) fn take_close_request(&self) -> bool {
+        self.shared.close_requested.swap(false, Ordering::Relaxed)
+    }
+
+    /// Starts a new session when the spinner is opened and returns its id.
+    pub(crate) fn begin_session(&self) -> u64 {
+        self.shared.session.fetch_add(1, Ordering::Relaxed) + 1
+    }
+
+    /// Forwards the cancel request of the user to the handles.
+    pub(crate) fn request_cancel(&self) {
+        self.shared.cancel_requested.store(true, Ordering::Release);
+    }
+
+    /// Resets the cancel request and its acknowledgement when the spinner is opened.
+    pub(crate) fn reset_cancel(&self) {
+        self.shared.cancel_requested.store(false, Ordering::Release);
+        self.shared
+            .cancel_acknowledged
+            .store(false, Ordering::Release);
+    }
+
+    /// Checks if a handle acknowledged the cancel request and resets the acknowledgement.
+    pub(crate) fn take_cancel_acknowledgement(&self) -> bool {
+        self.shared
+            .cancel_acknowledged
+            .swap(false, Ordering::Acquire)
+    }
+
+    /// Checks if a guard of the current session was dropped and resets the request.
+    pub(crate) fn take_guard_close(&self) -> bool {
+        let session = self.shared.guard_close.swap(0, Ordering::Relaxed);
+        session != 0 && session == self.shared.session.load(Ordering::Relaxed)
+    }
+
+    /// Checks if a handle or a guard of the current session requested the spinner to close,
+    /// without resetting the request.
+    pub(crate) fn peek_close_request(&self) -> bool {
+        let guard_session = self.shared.guard_close.load(Ordering::Relaxed);
+
+        self.shared.close_requested.load(Ordering::Relaxed)
+            || (guard_session != 0 && guard_session == self.shared.session.load(Ordering::Relaxed))
+    }
+
+    /// Gets the progress reported since the last update of the spinner, if any,
+    /// without resetting it.
+    pub(crate) fn peek_progress(&self) -> Option<f32> {
+        let bits = self.shared.progress.load(Ordering::Relaxed);
+        (bits != NO_PROGRESS).then(|| f32::from_bits(bits))
+    }
+
+    /// Gets the progress reported since the last call, if any.
+    pub(crate) fn take_progress(&self) -> Option<f32> {
+        let bits = self.shared.progress.swap(NO_PROGRESS, Ordering::Relaxed);
+        (bits != NO_PROGRESS).then(|| f32::from_bits(bits))
+    }
+
+    /// Gets the message set since the last call, if it was changed.
+    /// The inner value is None if the message was cleared.
+    #[allow(clippy::option_option)]
+    pub(crate) fn take_message(&self) -> Option<Option<String>> {
+        self.shared.message.lock().ok()?.take()
+    }
+
+    /// Gets the latest update of every item reported since the last call, ordered by index.
+    pub(crate) fn take_items(&self) -> Vec<ItemUpdate> {
+        self.shared
+            .items
+            .lock()
+            .map(|mut items| std::mem::take(&mut *items).into_values().collect())
+            .unwrap_or_default()
+    }
+}
+
+/// A guard that closes the spinner when it is dropped.
+///
+/// The guard is returned by [`ModalSpinner::open_guard`]. Holding it for the duration of a
+/// task ensures that the spinner is closed on every path the task can take, including early
+/// returns, the `?` operator and panics.
+///
+/// Like with a [`SpinnerHandle`], the close request is applied the next time the spinner is
+/// updated. A guard only closes the opening it was created for, so a guard dropped after the
+/// spinner was opened again does not close it.
+///
+/// # Example
+/// ```rust
+/// use std::thread;
+///
+/// use egui_modal_spinner::ModalSpinner;
+///
+/// fn load(path: &str) -> std::io::Result<String> {
+///     std::fs::read_to_string(path)
+/// }
+///
+/// let mut spinner = ModalSpinner::new();
+/// let guard = spinner.open_guard();
+///
+/// thread::spawn(move || -> std::io::Result<()> {
+///     let _guard = guard;
+///
+///     let _cats = load("cats.txt")?;
+///     let _dogs = load("dogs.txt")?;
+///
+///     Ok(())
+/// });
+/// ```
+///
+/// [`ModalSpinner`]: crate::ModalSpinner
+/// [`ModalSpinner::open_guard`]: crate::ModalSpinner::open_guard
+#[derive(Debug)]
+#[must_use = "the spinner is closed immediately if the guard is not stored"]
+pub struct SpinnerGuard {
+    handle: SpinnerHandle,
+    /// The session of the opening the guard was created for.
+    session: u64,
+}
+
+impl SpinnerGuard {
+    pub(crate) const fn new(handle: SpinnerHandle, session: u64) -> Self {
+        Self { handle, session }
+    }
+
+    /// Gets the handle of the guarded spinner, for example to report the progress.
+    pub const fn handle(&self) -> &SpinnerHandle {
+        &self.handle
+    }
+}
+
+impl Drop for SpinnerGuard {
+    fn drop(&mut self) {
+        self.handle
+            .shared
+            .guard_close
+            .store(self.session, Ordering::Relaxed);
+        self.handle.request_repaint();
+    }
+}
+
+/// A clonable, thread-safe progress value that a worker thread can update cheaply and that
+/// a [`ModalSpinner`] reads every frame to render its progress bar.
+///
+/// Updating the progress is a single atomic store. It neither allocates nor locks, and the
+/// spinner reads it without blocking the UI thread. Attach it to a spinner with
+/// [`ModalSpinner::progress_source`].
+///
+/// # Example
+/// ```rust
+/// use std::thread;
+///
+/// use egui_modal_spinner::{ModalSpinner, SpinnerProgress};
+///
+/// let progress = SpinnerProgress::new();
+/// let mut spinner = ModalSpinner::new().progress_source(progress.clone());
+///
+/// spinner.open();
+///
+/// thread::spawn(move || {
+///     for i in 0..100 {
+///         // Do some heavy resource task
+///         progress.set(i as f32 / 100.0);
+///     }
+/// });
+/// ```
+///
+/// [`ModalSpinner`]: crate::ModalSpinner
+/// [`ModalSpinner::progress_source`]: crate::ModalSpinner::progress_source
+#[derive(Debug, Clone)]
+pub struct SpinnerProgress {
+    /// The bits of the current progress, or `NO_PROGRESS`.
+    bits: Arc<AtomicU32>,
+}
+
+impl Default for SpinnerProgress {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SpinnerProgress {
+    /// Creates a new progress value without a progress.
+    pub fn new() -> Self {
+        Self {
+            bits: Arc::new(AtomicU32::new(NO_PROGRESS)),
+        }
+    }
+
+    /// Sets the progress between 0.0 and 1.0.
+    /// Values outside of this range are clamped, NaN is ignored.
+    pub fn set(&self, progress: f32) {
+        if progress.is_nan() {
+            return;
+        }
+
+        self.bits
+            .store(progress.clamp(0.0, 1.0).to_bits(), Ordering::Relaxed);
+    }
+
+    /// Gets the current progress, or None if no progress was set.
+    pub fn get(&self) -> Option<f32> {
+        let bits = self.bits.load(Ordering::Relaxed);
+        (bits != NO_PROGRESS).then(|| f32::from_bits(bits))
+    }
+
+    /// Clears the progress, so that the progress bar is no longer displayed.
+    pub fn clear(&self) {
+        self.bits.store(NO_PROGRESS, Ordering::Relaxed);
+    }
+}
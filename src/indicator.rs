@@ -0,0 +1,262 @@
+//! The animated indicator displayed in the center of the modal.
+
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::style::{OverlayStyle, SpinnerStyle};
+
+/// A custom animation displayed instead of the default spinner,
+/// see [`ModalSpinner::custom_spinner`].
+///
+/// The widget is added to a UI that is as large as the configured spinner size, so it can
+/// use `ui.available_size()` to size itself. It is called every frame the spinner is
+/// displayed, with the time elapsed since the spinner was opened and the reported progress,
+/// if any. The modal, the fade animations and the timing logic stay the same.
+///
+/// The trait is implemented for closures with the same signature as [`SpinnerWidget::ui`].
+///
+/// # Example
+/// ```rust
+/// use std::time::Duration;
+///
+/// use egui_modal_spinner::ModalSpinner;
+///
+/// let spinner = ModalSpinner::new().custom_spinner(
+///     |ui: &mut egui::Ui, elapsed: Duration, _progress: Option<f32>| {
+///         let dots = ".".repeat(elapsed.as_millis() as usize / 300 % 4);
+///         ui.label(format!("Loading{dots}"));
+///         ui.ctx().request_repaint();
+///     },
+/// );
+/// ```
+///
+/// [`ModalSpinner::custom_spinner`]: crate::ModalSpinner::custom_spinner
+pub trait SpinnerWidget: Send + Sync {
+    /// Adds the widget to the given UI.
+    fn ui(&self, ui: &mut egui::Ui, elapsed: Duration, progress: Option<f32>);
+}
+
+impl<F> SpinnerWidget for F
+where
+    F: Fn(&mut egui::Ui, Duration, Option<f32>) + Send + Sync,
+{
+    fn ui(&self, ui: &mut egui::Ui, elapsed: Duration, progress: Option<f32>) {
+        self(ui, elapsed, progress);
+    }
+}
+
+/// A clonable custom spinner widget stored in the spinner.
+#[derive(Clone)]
+pub struct CustomSpinner(Arc<dyn SpinnerWidget>);
+
+impl CustomSpinner {
+    pub fn new(widget: impl SpinnerWidget + 'static) -> Self {
+        Self(Arc::new(widget))
+    }
+
+    /// Adds the widget inside a UI with the given size, centered horizontally.
+    pub fn ui(&self, ui: &mut egui::Ui, size: f32, elapsed: Duration, progress: Option<f32>) {
+        ui.allocate_ui_with_layout(
+            egui::Vec2::splat(size),
+            egui::Layout::top_down(egui::Align::Center),
+            |ui| {
+                ui.set_min_size(egui::Vec2::splat(size));
+                self.0.ui(ui, elapsed, progress);
+            },
+        );
+    }
+}
+
+impl fmt::Debug for CustomSpinner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("CustomSpinner")
+    }
+}
+
+/// Adds the spinner widget with the given size, styled by the given overlay style.
+pub fn ui_spinner(ui: &mut egui::Ui, style: &OverlayStyle, size: f32) {
+    let color = style.spinner_color(ui.visuals());
+    ui_spinner_with_color(ui, style.spinner_style, size, color);
+}
+
+/// Adds a spinner with the given animation, size and color.
+pub fn ui_spinner_with_color(
+    ui: &mut egui::Ui,
+    spinner_style: SpinnerStyle,
+    size: f32,
+    color: egui::Color32,
+) {
+    if spinner_style == SpinnerStyle::Circle {
+        ui.add(egui::Spinner::new().size(size).color(color));
+        return;
+    }
+
+    let (rect, _) = ui.allocate_exact_size(egui::vec2(size, size), egui::Sense::hover());
+
+    if !ui.is_rect_visible(rect) {
+        return;
+    }
+
+    ui.ctx().request_repaint();
+
+    #[allow(clippy::cast_possible_truncation)]
+    let time = ui.input(|i| i.time) as f32;
+    let painter = ui.painter();
+
+    match spinner_style {
+        SpinnerStyle::Circle => {}
+        SpinnerStyle::Dots => paint_dots(painter, rect, time, color),
+        SpinnerStyle::Bars => paint_bars(painter, rect, time, color),
+        SpinnerStyle::Ring => paint_ring(painter, rect, time, color),
+        SpinnerStyle::Pulse => paint_pulse(painter, rect, time, color),
+    }
+}
+
+/// Gets a value oscillating between 0.0 and 1.0 once per second,
+/// shifted by the given share of the period.
+fn wave(time: f32, offset: f32) -> f32 {
+    ((time - offset) * std::f32::consts::TAU)
+        .sin()
+        .mul_add(0.5, 0.5)
+}
+
+fn paint_dots(painter: &egui::Painter, rect: egui::Rect, time: f32, color: egui::Color32) {
+    const DOTS: usize = 3;
+
+    let spacing = rect.width() / 3.0;
+    let max_radius = spacing / 2.0 * 0.8;
+
+    for i in 0..DOTS {
+        #[allow(clippy::cast_precision_loss)]
+        let i = i as f32;
+
+        let center = egui::pos2((i + 0.5).mul_add(spacing, rect.left()), rect.center().y);
+        let radius = max_radius * wave(time, i / 6.0).mul_add(0.6, 0.4);
+
+        painter.circle_filled(center, radius, color);
+    }
+}
+
+fn paint_bars(painter: &egui::Painter, rect: egui::Rect, time: f32, color: egui::Color32) {
+    const BARS: usize = 4;
+
+    let pitch = rect.width() / 4.0;
+    let width = pitch * 0.6;
+
+    for i in 0..BARS {
+        #[allow(clippy::cast_precision_loss)]
+        let i = i as f32;
+
+        let height = rect.height() * wave(time, i / 8.0).mul_add(0.7, 0.3);
+        let bar = egui::Rect::from_center_size(
+            egui::pos2((i + 0.5).mul_add(pitch, rect.left()), rect.center().y),
+            egui::vec2(width, height),
+        );
+
+        painter.rect_filled(bar, width / 4.0, color);
+    }
+}
+
+fn paint_ring(painter: &egui::Painter, rect: egui::Rect, time: f32, color: egui::Color32) {
+    /// The number of segments of the arc.
+    const SEGMENTS: u16 = 24;
+
+    let width = rect.width() / 8.0;
+    let radius = rect.width() / 2.0 - width;
+
+    painter.circle_stroke(
+        rect.center(),
+        radius,
+        egui::Stroke::new(width, color.gamma_multiply(0.25)),
+    );
+
+    // The arc spans a quarter of the ring and rotates once per second.
+    let start = time * std::f32::consts::TAU;
+    let points = (0..=SEGMENTS)
+        .map(|i| {
+            let angle =
+                (f32::from(i) / f32::from(SEGMENTS)).mul_add(std::f32::consts::FRAC_PI_2, start);
+            rect.center() + radius * egui::vec2(angle.cos(), angle.sin())
+        })
+        .collect();
+
+    painter.add(egui::Shape::line(points, egui::Stroke::new(width, color)));
+}
+
+fn paint_pulse(painter: &egui::Painter, rect: egui::Rect, time: f32, color: egui::Color32) {
+    let max_radius = rect.width() / 2.0;
+
+    // The ring expands from the dot to the edge once per second.
+    let expansion = time.fract();
+    let radius = max_radius * expansion.mul_add(0.75, 0.25);
+
+    painter.circle_stroke(
+        rect.center(),
+        radius,
+        egui::Stroke::new(max_radius / 6.0, color.gamma_multiply(1.0 - expansion)),
+    );
+    painter.circle_filled(rect.center(), max_radius / 4.0, color);
+}
+
+/// The result of the task displayed by the completion animation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Completion {
+    Success,
+    Failure,
+}
+
+/// The width of the strokes of the completion animation.
+const COMPLETION_STROKE_WIDTH: f32 = 3.0;
+
+/// Adds a checkmark or a cross with the given size, drawn up to the given progress
+/// between 0.0 and 1.0.
+///
+/// The checkmark uses the spinner color, the cross uses the error color of the current
+/// visuals.
+pub fn ui_completion(
+    ui: &mut egui::Ui,
+    style: &OverlayStyle,
+    size: f32,
+    completion: Completion,
+    progress: f32,
+) {
+    let (rect, _) = ui.allocate_exact_size(egui::vec2(size, size), egui::Sense::hover());
+
+    if !ui.is_rect_visible(rect) {
+        return;
+    }
+
+    let point = |x: f32, y: f32| rect.lerp_inside(egui::vec2(x, y));
+
+    let (color, strokes) = match completion {
+        Completion::Success => (
+            style.spinner_color(ui.visuals()),
+            [
+                [point(0.2, 0.55), point(0.42, 0.75)],
+                [point(0.42, 0.75), point(0.8, 0.3)],
+            ],
+        ),
+        Completion::Failure => (
+            ui.visuals().error_fg_color,
+            [
+                [point(0.25, 0.25), point(0.75, 0.75)],
+                [point(0.75, 0.25), point(0.25, 0.75)],
+            ],
+        ),
+    };
+
+    let stroke = egui::Stroke::new(COMPLETION_STROKE_WIDTH, color);
+    let progress = progress.clamp(0.0, 1.0);
+
+    // The strokes are drawn one after the other, each during half of the animation.
+    for (i, [start, end]) in strokes.into_iter().enumerate() {
+        #[allow(clippy::cast_precision_loss)]
+        let t = progress.mul_add(2.0, -(i as f32)).clamp(0.0, 1.0);
+
+        if t > 0.0 {
+            ui.painter()
+                .line_segment([start, start.lerp(end, t)], stroke);
+        }
+    }
+}
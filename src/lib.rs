@@ -72,6 +72,11 @@
 //! }
 //! ```
 //!
+//! The manual polling of the receiver can also be replaced with
+//! [`ModalSpinner::update_with_receiver`], which closes the spinner once the thread sends
+//! its result and returns the received value.
+//! [`ModalSpinner::show_while`] additionally spawns the thread and opens the spinner.
+//!
 //! # Configuration
 //! The following example shows the possible configuration options.
 //! ```rust
@@ -79,251 +84,85 @@
 //!
 //! let spinner = ModalSpinner::new()
 //!     .id("My custom spinner")
+//!     .order(egui::Order::Foreground)
 //!     .fill_color(egui::Color32::BLUE)
+//!     .rounding(8.0)
 //!     .fade_in(false)
 //!     .fade_out(true)
 //!     .spinner_size(40.0)
 //!     .spinner_color(egui::Color32::RED)
-//!     .show_elapsed_time(false);
+//!     .show_elapsed_time(false)
+//!     .elapsed_label("Time: {} seconds")
+//!     .show_progress_percentage(true)
+//!     .show_cancel_button(true)
+//!     .cancel_label("Abort");
 //! ```
+//!
+//! # Cargo features
+//! - `debug-tools`: Enables tools that help debugging the integration of the spinner,
+//!   like `ModalSpinner::show_frame_stats` and `style_editor_ui`.
+//! - `serde`: Enables serialization of [`OverlayStyle`].
+//! - `frame-capture`: Enables `ModalSpinner::freeze_background` to display the last rendered
+//!   frame frozen beneath the modal.
+//! - `testing`: Enables `ModalSpinner::advance_time` to unit test the state of the spinner
+//!   with a simulated clock.
+//!
+//! # Panics
+//! The spinner blocks the whole application while it is open, so a panic inside the spinner
+//! would take the entire application down with it. None of the methods provided by this crate
+//! panic. Invalid configuration values are ignored and the respective default is used instead.
+//! This includes durations and frame rates that are out of the range of [`std::time::Duration`].
+//! If you need to know whether a value was accepted, use the `try_` variant of the
+//! configuration method, e.g. [`ModalSpinner::try_spinner_size`].
 
 #![warn(missing_docs)] // Let's keep the public API well documented!
 
-use std::time::SystemTime;
-
-use egui::Widget;
-
-/// Represents the state the spinner is currently in.
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub enum SpinnerState {
-    /// The spinner is currently closed and not visible.
-    Closed,
-    /// The spinner is currently open and user input is suppressed.
-    Open,
-}
-
-/// Represents a spinner instance.
-#[derive(Debug, Clone)]
-pub struct ModalSpinner {
-    /// Represents the state of the spinner.
-    state: SpinnerState,
-    /// If the modal is closed but currently fading out.
-    fading_out: bool,
-    /// Timestamp when the spinner was opened.
-    timestamp: SystemTime,
-
-    /// The ID of the modal area. If None, a default is used.
-    id: Option<egui::Id>,
-    /// The fill color of the modal background.
-    fill_color: Option<egui::Color32>,
-    /// If the modal window should fade in when opening.
-    fade_in: bool,
-    /// If the modal should fade out when closing.
-    fade_out: bool,
-    /// Configuration of the spinner.
-    spinner: Spinner,
-    /// If the time elapsed since opening should be displayed under the spinner.
-    show_elapsed_time: bool,
-}
-
-impl Default for ModalSpinner {
-    fn default() -> Self {
-        Self::new()
-    }
-}
-
-/// Creation methods
-impl ModalSpinner {
-    /// Creates a new spinner instance.
-    pub fn new() -> Self {
-        Self {
-            state: SpinnerState::Closed,
-            fading_out: false,
-            timestamp: SystemTime::now(),
-
-            id: None,
-            fill_color: None,
-            fade_in: true,
-            fade_out: true,
-            spinner: Spinner::default(),
-            show_elapsed_time: true,
-        }
-    }
-
-    /// Sets the ID of the spinner.
-    pub fn id(mut self, id: impl Into<egui::Id>) -> Self {
-        self.id = Some(id.into());
-        self
-    }
-
-    /// Sets the fill color of the modal background.
-    pub fn fill_color(mut self, color: impl Into<egui::Color32>) -> Self {
-        self.fill_color = Some(color.into());
-        self
-    }
-
-    /// If the modal should fade in.
-    pub const fn fade_in(mut self, fade_in: bool) -> Self {
-        self.fade_in = fade_in;
-        self
-    }
-
-    /// If the modal should fade out.
-    pub const fn fade_out(mut self, fade_out: bool) -> Self {
-        self.fade_out = fade_out;
-        self
-    }
-
-    /// Sets the size of the spinner.
-    pub const fn spinner_size(mut self, size: f32) -> Self {
-        self.spinner.size = Some(size);
-        self
-    }
-
-    /// Sets the color of the spinner.
-    pub fn spinner_color(mut self, color: impl Into<egui::Color32>) -> Self {
-        self.spinner.color = Some(color.into());
-        self
-    }
-
-    /// If the elapsed time should be displayed below the spinner.
-    pub const fn show_elapsed_time(mut self, show_elapsed_time: bool) -> Self {
-        self.show_elapsed_time = show_elapsed_time;
-        self
-    }
-}
-
-/// Getter and setter
-impl ModalSpinner {
-    /// Gets the current state of the spinner.
-    pub const fn state(&self) -> &SpinnerState {
-        &self.state
-    }
-}
-
-/// Implementation methods
-impl ModalSpinner {
-    /// Opens the spinner.
-    pub fn open(&mut self) {
-        self.state = SpinnerState::Open;
-        self.timestamp = SystemTime::now();
-    }
-
-    /// Closes the spinner.
-    pub fn close(&mut self) {
-        self.state = SpinnerState::Closed;
-        self.fading_out = self.fade_out;
-    }
-
-    /// Main update method of the spinner that should be called every frame if you want the
-    /// spinner to be visible.
-    ///
-    /// This has no effect if the `SpinnerState` is currently not `SpinnerState::Open`.
-    pub fn update(&mut self, ctx: &egui::Context) {
-        self.update_ui(ctx, |_| ());
-    }
-
-    /// Main update method of the spinner that should be called every frame if you want the
-    /// spinner to be visible.
-    ///
-    /// This method allows additional content to be displayed under the
-    /// spinner - or if activated - under the elapsed time.
-    /// However, note that the additional content is not taken into account when
-    /// centering the spinner. Therefore, a large amount of additional
-    /// content on the Y-axis is not recommended.
-    ///
-    /// This has no effect if the `SpinnerState` is currently not `SpinnerState::Open`.
-    pub fn update_with_content(&mut self, ctx: &egui::Context, ui: impl FnOnce(&mut egui::Ui)) {
-        self.update_ui(ctx, ui);
-    }
-}
-
-/// UI methods
-impl ModalSpinner {
-    fn update_ui(&mut self, ctx: &egui::Context, content: impl FnOnce(&mut egui::Ui)) {
-        if self.state != SpinnerState::Open && !self.fading_out {
-            return;
-        }
-
-        let id = self.id.unwrap_or_else(|| egui::Id::from("_modal_spinner"));
-        let screen_rect = ctx.input(|i| i.screen_rect);
-
-        let opacity = ctx.animate_bool_with_easing(
-            id.with("fade_out"),
-            self.state == SpinnerState::Open,
-            egui::emath::easing::cubic_out,
-        );
-
-        if opacity <= 0.0 && self.fading_out {
-            self.fading_out = false;
-            return;
-        }
-
-        let re = egui::Area::new(id)
-            .movable(false)
-            .interactable(true)
-            .fixed_pos(screen_rect.left_top())
-            .fade_in(self.fade_in)
-            .show(ctx, |ui| {
-                if self.fading_out {
-                    ui.multiply_opacity(opacity);
-                }
-
-                let fill_color = self.fill_color.unwrap_or_else(|| {
-                    if ctx.style().visuals.dark_mode {
-                        egui::Color32::from_black_alpha(120)
-                    } else {
-                        egui::Color32::from_white_alpha(40)
-                    }
-                });
-
-                ui.painter()
-                    .rect_filled(screen_rect, egui::Rounding::ZERO, fill_color);
-
-                ui.allocate_response(screen_rect.size(), egui::Sense::click());
-
-                let child_ui = egui::UiBuilder::new()
-                    .max_rect(screen_rect)
-                    .layout(egui::Layout::top_down(egui::Align::Center));
-
-                ui.allocate_new_ui(child_ui, |ui| {
-                    self.ui_update_spinner(ui, &screen_rect);
-                    content(ui);
-                });
-            });
-
-        ctx.move_to_top(re.response.layer_id);
-    }
-
-    fn ui_update_spinner(&self, ui: &mut egui::Ui, screen_rect: &egui::Rect) {
-        let spinner_h = self
-            .spinner
-            .size
-            .unwrap_or_else(|| ui.style().spacing.interact_size.y);
-
-        let mut margin = screen_rect.height() / 2.0 - spinner_h / 2.0;
-
-        if self.show_elapsed_time {
-            let height = ui.fonts(|f| f.row_height(&egui::TextStyle::Body.resolve(ui.style())));
-            margin -= ui.spacing().item_spacing.y.mul_add(2.0, height / 2.0);
-        }
-
-        ui.add_space(margin);
-
-        self.spinner.update(ui);
-
-        if self.show_elapsed_time {
-            self.ui_update_elapsed_time(ui);
-        }
-    }
-
-    fn ui_update_elapsed_time(&self, ui: &mut egui::Ui) {
-        ui.add_space(ui.spacing().item_spacing.y);
-        ui.label(format!(
-            "Elapsed: {} s",
-            self.timestamp.elapsed().unwrap_or_default().as_secs()
-        ));
-    }
+mod animation;
+mod batch;
+#[cfg(feature = "frame-capture")]
+mod capture;
+mod error;
+pub mod graph;
+pub mod handle;
+mod indicator;
+pub mod overlay;
+pub mod plugin;
+mod progress;
+mod steps;
+pub mod style;
+pub mod task;
+mod utils;
+
+pub use animation::AnimatedImage;
+pub use batch::{ItemFailure, ItemState};
+pub use error::ConfigError;
+pub use graph::{TaskGraph, TaskId};
+pub use handle::{HandleStats, SpinnerGuard, SpinnerHandle, SpinnerProgress};
+pub use indicator::SpinnerWidget;
+pub use overlay::{
+    ElapsedFormat, ErrorAction, ModalSpinner, OverlayVisibility, SpinnerCtx, SpinnerPhase,
+    SpinnerState, ViewportCoverage,
+};
+pub use plugin::ModalSpinnerPlugin;
+pub use steps::StepState;
+#[cfg(feature = "debug-tools")]
+pub use style::style_editor_ui;
+pub use style::{OverlayStyle, SpinnerStyle};
+pub use task::{CooperativeTask, TaskStep};
+
+/// Re-exports the types that are commonly needed when using the spinner.
+///
+/// ```rust
+/// use egui_modal_spinner::prelude::*;
+///
+/// let spinner = ModalSpinner::new().overlay_style(OverlayStyle::default());
+/// ```
+pub mod prelude {
+    pub use crate::handle::{SpinnerGuard, SpinnerHandle, SpinnerProgress};
+    pub use crate::overlay::{ModalSpinner, SpinnerCtx, SpinnerPhase, SpinnerState};
+    pub use crate::plugin::ModalSpinnerPlugin;
+    pub use crate::style::{OverlayStyle, SpinnerStyle};
+    pub use crate::task::{CooperativeTask, TaskStep};
 }
 
 /// This tests if the spinner is send and sync.
@@ -334,26 +173,3 @@ const fn test_prop<T: Send + Sync>() {}
 const fn test() {
     test_prop::<ModalSpinner>();
 }
-
-/// Wrapper above `egui::Spinner` to be able to customize trait implementations.
-#[derive(Debug, Default, Clone, PartialEq)]
-struct Spinner {
-    pub size: Option<f32>,
-    pub color: Option<egui::Color32>,
-}
-
-impl Spinner {
-    fn update(&self, ui: &mut egui::Ui) -> egui::Response {
-        let mut spinner = egui::Spinner::new();
-
-        if let Some(size) = self.size {
-            spinner = spinner.size(size);
-        }
-
-        if let Some(color) = self.color {
-            spinner = spinner.color(color);
-        }
-
-        spinner.ui(ui)
-    }
-}
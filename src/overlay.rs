@@ -0,0 +1,3153 @@
+//! The modal overlay suppressing user input while it is open.
+
+use std::panic;
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+use web_time::Instant;
+
+use crate::batch::{self, ItemFailure, ItemState};
+#[cfg(feature = "frame-capture")]
+use crate::capture::FrameCapture;
+use crate::graph::TaskGraph;
+use crate::handle::{OwnedHandle, SpinnerGuard, SpinnerHandle, SpinnerProgress};
+use crate::indicator::{self, Completion, CustomSpinner, SpinnerWidget};
+use crate::plugin::{ModalSpinnerPlugin, PluginLink, PluginView};
+use crate::progress;
+use crate::steps::{self, Step, StepRow, StepState};
+use crate::style::{OverlayStyle, SpinnerStyle};
+use crate::task::CooperativeTask;
+#[cfg(feature = "debug-tools")]
+use crate::utils::available_font_id;
+use crate::utils::{is_positive_finite, row_height, Callback};
+use crate::ConfigError;
+
+/// The ID egui uses to store the state of the currently open context menu.
+/// egui has no public API to close a context menu from outside of it. The tests check that
+/// the ID still matches using `egui::Context::is_context_menu_open`.
+const CONTEXT_MENU_ID: &str = "__egui::context_menu";
+
+/// The ID used to store the last pass in which a spinner was open or fading out.
+const LAST_ACTIVE_ID: &str = "egui_modal_spinner_last_active";
+
+/// Represents the state the spinner is currently in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SpinnerState {
+    /// The spinner is currently closed and not visible.
+    Closed,
+    /// The spinner is currently open and user input is suppressed.
+    Open,
+    /// The task failed and the modal displays the error, see [`ModalSpinner::fail`].
+    /// User input is suppressed, except for the buttons to retry or dismiss the task.
+    Error,
+}
+
+/// The button of the error state clicked by the user, see [`ModalSpinner::fail`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorAction {
+    /// The user wants to retry the task.
+    Retry,
+    /// The user dismissed the error and the spinner was closed.
+    Dismiss,
+}
+
+/// The phase of the modal animation the spinner is currently in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpinnerPhase {
+    /// The spinner is open and the modal is fading in.
+    FadingIn,
+    /// The spinner is open and the modal is fully visible.
+    Open,
+    /// The spinner is closed and the modal is fading out.
+    FadingOut,
+}
+
+/// The format of the elapsed time inserted into the elapsed time label,
+/// see [`ModalSpinner::elapsed_format`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ElapsedFormat {
+    /// The number of elapsed seconds, e.g. `237`.
+    #[default]
+    Seconds,
+    /// The elapsed minutes and seconds, e.g. `03:57`.
+    MinutesSeconds,
+    /// The elapsed hours, minutes and seconds, e.g. `00:03:57`.
+    HoursMinutesSeconds,
+    /// The number of elapsed milliseconds, e.g. `237016`.
+    Milliseconds,
+}
+
+impl ElapsedFormat {
+    /// Formats the given elapsed time.
+    pub fn format(self, elapsed: Duration) -> String {
+        let seconds = elapsed.as_secs();
+
+        match self {
+            Self::Seconds => seconds.to_string(),
+            Self::MinutesSeconds => format!("{:02}:{:02}", seconds / 60, seconds % 60),
+            Self::HoursMinutesSeconds => format!(
+                "{:02}:{:02}:{:02}",
+                seconds / 3600,
+                seconds / 60 % 60,
+                seconds % 60
+            ),
+            Self::Milliseconds => elapsed.as_millis().to_string(),
+        }
+    }
+}
+
+/// How the modal is displayed when the spinner is updated inside a secondary viewport,
+/// see [`ModalSpinner::secondary_viewports`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ViewportCoverage {
+    /// The full modal including the spinner and the additional content is displayed.
+    /// The state of the spinner is also updated from the viewport.
+    #[default]
+    Full,
+    /// Only the background of the modal is displayed, which blocks the input to the viewport.
+    /// The state of the spinner is only updated from the main viewport.
+    DimOnly,
+    /// Nothing is displayed and the viewport stays interactive.
+    Disabled,
+}
+
+/// The state of the spinner passed to the content closure of
+/// [`ModalSpinner::update_with_spinner_ctx`].
+///
+/// This allows the additional content to reflect the state of the spinner
+/// without duplicating it in the application.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct SpinnerCtx {
+    /// The time elapsed since the spinner was opened.
+    pub elapsed: Duration,
+    /// The progress of the task between 0.0 and 1.0, if set.
+    pub progress: Option<f32>,
+    /// The phase of the modal animation.
+    pub phase: SpinnerPhase,
+    /// If the user requested to cancel the task using the cancel button.
+    pub cancel_requested: bool,
+}
+
+/// The area covered by the modal, reported every frame with
+/// [`ModalSpinner::on_visibility`].
+///
+/// This allows applications that capture the screen to know when and how much the content
+/// beneath the modal is obscured.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+pub struct OverlayVisibility {
+    /// The rect covered by the modal.
+    pub rect: egui::Rect,
+    /// The opacity of the modal between 0.0 (hidden) and 1.0 (fully visible).
+    pub opacity: f32,
+}
+
+/// Represents a spinner instance.
+#[derive(Debug, Clone)]
+pub struct ModalSpinner {
+    /// Represents the state of the spinner.
+    state: SpinnerState,
+    /// If the modal is closed but currently fading out.
+    fading_out: bool,
+    /// If the spinner was closed before the minimum display time elapsed,
+    /// and should close once it did.
+    close_pending: bool,
+    /// If the modal was painted since the spinner was opened.
+    /// False while the spinner is open for less than the show delay.
+    revealed: bool,
+    /// The widget that had the keyboard focus when the modal opened.
+    /// The focus is given back to it once the modal no longer suppresses input.
+    focus_before_open: Option<egui::Id>,
+    /// If painting the modal was skipped during the last update, because the screen rect
+    /// was empty, e.g. while the window is minimized.
+    rendering_skipped: bool,
+    /// If panics of the content closure are caught, see [`ModalSpinner::catch_content_panics`].
+    catch_content_panics: bool,
+    /// The message of the last panic of the content closure that was not yet taken.
+    content_panic: Option<String>,
+    /// If the content closure panicked during the last update.
+    content_panicked: bool,
+    /// The note displayed instead of the content after it panicked.
+    content_panic_label: String,
+    /// If the spinner was opened, but the context was not yet updated to
+    /// reflect that the modal is open.
+    pending_open: bool,
+    /// Timestamp when the spinner was opened.
+    timestamp: Instant,
+    /// The time of the context when the modal was first shown after opening.
+    opened_at: f64,
+    /// The linear progress of the fade animation, from 0.0 (hidden) to 1.0 (fully visible).
+    /// A single value is used for fading in and out, so that reversing the direction
+    /// continues from the current opacity.
+    fade: f32,
+    /// The time of the context when the fade animation was last advanced.
+    /// None if the modal was not painted since it was fully hidden.
+    fade_time: Option<f64>,
+    /// Timestamp of the last update while the spinner was open.
+    /// None if the spinner has not been updated since it was opened.
+    last_update: Option<Instant>,
+    /// If the user requested to cancel the task using the cancel button.
+    cancel_requested: bool,
+    /// If a worker acknowledged that the task stopped after the cancel request.
+    cancel_acknowledged: bool,
+    /// The progress of the task between 0.0 and 1.0. None if the progress is not known.
+    progress: Option<f32>,
+    /// The last progress used to estimate the progress rate, and the time of the context
+    /// when it was first observed.
+    progress_sample: Option<(f64, f32)>,
+    /// The smoothed progress per second, used to estimate the remaining time.
+    progress_rate: Option<f32>,
+    /// The status message displayed beneath the spinner.
+    message: Option<String>,
+    /// The steps of the task displayed beneath the spinner.
+    steps: Vec<Step>,
+    /// The graph of dependent tasks whose active tasks are displayed beneath the spinner.
+    task_graph: Option<TaskGraph>,
+    /// The states of the items of a batch operation, displayed as a mini-map.
+    items: Vec<ItemState>,
+    /// The items that failed since the spinner was opened.
+    failures: Vec<ItemFailure>,
+    /// If a summary of the failed items is displayed when the spinner is closed.
+    failure_summary: bool,
+    /// The text of the failure summary, where `{}` is replaced by the number of failures.
+    failure_label: String,
+    /// The text of the header that expands the list of failures.
+    failure_details_label: String,
+
+    /// The ID of the modal area. If None, the default ID is used.
+    id: Option<egui::Id>,
+    /// The ID used if no ID was set, derived from the location the spinner was created at.
+    default_id: egui::Id,
+    /// The layer order the modal area is painted on.
+    order: egui::Order,
+    /// If the modal window should fade in when opening.
+    fade_in: bool,
+    /// If the modal should fade out when closing.
+    fade_out: bool,
+    /// The visual configuration of the overlay.
+    style: OverlayStyle,
+    /// If the time elapsed since opening should be displayed under the spinner.
+    show_elapsed_time: bool,
+    /// If the percentage should be displayed inside the progress bar.
+    show_progress_percentage: bool,
+    /// If the estimated remaining time should be displayed below the progress bar.
+    show_eta: bool,
+    /// Template of the remaining time label. `{}` is replaced by the remaining seconds.
+    eta_label: String,
+    /// The result displayed instead of the spinner before the modal closes, if any.
+    completion: Option<Completion>,
+    /// The time at which the completion animation started.
+    completion_started: Option<f64>,
+    /// The time the result is displayed before the modal closes.
+    completion_duration: Duration,
+    /// The error displayed instead of the spinner while in the error state.
+    error: Option<String>,
+    /// The button of the error state clicked by the user and not yet taken.
+    error_action: Option<ErrorAction>,
+    /// The text of the button to retry the task in the error state.
+    retry_label: String,
+    /// The text of the button to dismiss the error in the error state.
+    dismiss_label: String,
+    /// If the spinner is displayed in a modal blocking the input. If false, it is only
+    /// displayed in a corner of the screen.
+    modal: bool,
+    /// The corner of the screen the spinner is displayed in while it does not block the input.
+    indicator_corner: egui::Align2,
+    /// If a button to continue the task in the background should be displayed.
+    show_background_button: bool,
+    /// The text of the button to continue the task in the background.
+    background_label: String,
+    /// If the user moved the task to the background, so the spinner is only displayed
+    /// in a corner of the screen without blocking the input.
+    in_background: bool,
+    /// If a button to cancel the task should be displayed below the spinner.
+    show_cancel_button: bool,
+    /// The text of the cancel button.
+    cancel_label: String,
+    /// The text of the cancel button while waiting for the cancel request to be acknowledged.
+    cancelling_label: String,
+    /// The delay after which the hint to cancel using the escape key is displayed.
+    /// None if the hint is not displayed.
+    escape_hint: Option<Duration>,
+    /// The text of the hint to cancel using the escape key.
+    escape_hint_label: String,
+    /// Template of the elapsed time label. `{}` is replaced by the elapsed seconds.
+    elapsed_label: String,
+    /// The format of the elapsed time inserted into the template.
+    elapsed_format: ElapsedFormat,
+    /// Formats the elapsed time label. If set, it is used instead of the template.
+    elapsed_formatter: Option<Callback<Duration, String>>,
+    /// How the modal is displayed inside secondary viewports.
+    secondary_viewports: ViewportCoverage,
+    /// Time after which the open spinner is considered stale if it was not updated.
+    stale_threshold: Duration,
+    /// The minimum time the spinner stays open, even if it is closed earlier.
+    min_display_time: Duration,
+    /// The time after which the open spinner is closed automatically.
+    timeout: Option<Duration>,
+    /// If the spinner was closed automatically because the timeout elapsed.
+    timed_out: bool,
+    /// The time the spinner has to be open before the modal is painted.
+    show_after: Duration,
+    /// The elapsed times, in ascending order, at which a milestone is reported.
+    milestones: Vec<Duration>,
+    /// The index of the next milestone that was not yet reached.
+    next_milestone: usize,
+    /// The milestones reached since they were last taken.
+    reached_milestones: Vec<Duration>,
+
+    /// If the frame rate and frame time should be displayed in the top left corner.
+    #[cfg(feature = "debug-tools")]
+    show_frame_stats: bool,
+    /// Timestamps and durations of the recent frames, used to display the frame stats.
+    #[cfg(feature = "debug-tools")]
+    frame_times: egui::util::History<f32>,
+
+    /// If the last rendered frame should be displayed frozen beneath the modal.
+    #[cfg(feature = "frame-capture")]
+    freeze_background: bool,
+    /// If the frozen background should be displayed in grayscale.
+    #[cfg(feature = "frame-capture")]
+    desaturate_background: bool,
+    /// The frame captured when the spinner was opened.
+    #[cfg(feature = "frame-capture")]
+    frame_capture: FrameCapture,
+
+    /// Link to the registration of the spinner with the [`ModalSpinnerPlugin`].
+    plugin_link: PluginLink,
+    /// The handle used to control the spinner from worker threads.
+    handle: OwnedHandle,
+    /// The session of the handle started by the last opening of the spinner.
+    session: u64,
+    /// The named sources that keep the spinner open, see [`ModalSpinner::open_for`].
+    sources: Vec<String>,
+    /// The progress value read every frame while the spinner is open.
+    progress_source: Option<SpinnerProgress>,
+    /// Called every frame the modal is active with the area it covers.
+    on_visibility: Option<Callback<OverlayVisibility>>,
+    /// The widget displayed instead of the default spinner, if set.
+    custom_spinner: Option<CustomSpinner>,
+    /// Called when the spinner is opened while the modal is not displayed.
+    on_open: Option<Callback<()>>,
+    /// Called when the modal is gone after the spinner was closed.
+    on_close: Option<Callback<()>>,
+    /// If `on_open` was called and `on_close` was not yet called since then.
+    lifecycle_open: bool,
+    /// The simulated clock advanced by [`ModalSpinner::advance_time`].
+    #[cfg(feature = "testing")]
+    test_clock: TestClock,
+}
+
+/// The simulated clock used to test the spinner without rendering frames.
+#[cfg(feature = "testing")]
+#[derive(Debug, Clone, Default)]
+struct TestClock {
+    /// The time the clock was advanced by, added to the time of the system clock.
+    offset: Duration,
+    /// The context the spinner is updated with when the clock is advanced.
+    ctx: Option<egui::Context>,
+}
+
+impl Default for ModalSpinner {
+    #[track_caller]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Creation methods
+impl ModalSpinner {
+    /// Creates a new spinner instance.
+    ///
+    /// Unless an ID is set with [`ModalSpinner::id`], the ID of the spinner is derived from
+    /// the location in the source code this method is called from. This way, the ID stays the
+    /// same between runs of the application, and spinners created in different places don't
+    /// share their state in egui.
+    #[track_caller]
+    pub fn new() -> Self {
+        let location = std::panic::Location::caller();
+
+        Self {
+            state: SpinnerState::Closed,
+            fading_out: false,
+            close_pending: false,
+            revealed: false,
+            focus_before_open: None,
+            rendering_skipped: false,
+            catch_content_panics: true,
+            content_panic: None,
+            content_panicked: false,
+            content_panic_label: "The content could not be displayed".to_owned(),
+            pending_open: false,
+            timestamp: Instant::now(),
+            opened_at: 0.0,
+            fade: 0.0,
+            fade_time: None,
+            last_update: None,
+            cancel_requested: false,
+            cancel_acknowledged: false,
+            progress: None,
+            progress_sample: None,
+            progress_rate: None,
+            message: None,
+            steps: Vec::new(),
+            task_graph: None,
+            items: Vec::new(),
+            failures: Vec::new(),
+            failure_summary: true,
+            failure_label: "{} items failed".to_owned(),
+            failure_details_label: "Details".to_owned(),
+
+            id: None,
+            default_id: egui::Id::new((
+                "egui_modal_spinner",
+                location.file(),
+                location.line(),
+                location.column(),
+            )),
+            order: egui::Order::Foreground,
+            fade_in: true,
+            fade_out: true,
+            style: OverlayStyle::default(),
+            show_elapsed_time: true,
+            show_progress_percentage: true,
+            show_eta: false,
+            eta_label: "Remaining: {} s".to_owned(),
+            completion: None,
+            completion_started: None,
+            completion_duration: Duration::from_millis(600),
+            error: None,
+            error_action: None,
+            retry_label: "Retry".to_owned(),
+            dismiss_label: "Dismiss".to_owned(),
+            modal: true,
+            indicator_corner: egui::Align2::RIGHT_BOTTOM,
+            show_background_button: false,
+            background_label: "Run in background".to_owned(),
+            in_background: false,
+            show_cancel_button: false,
+            cancel_label: "Cancel".to_owned(),
+            cancelling_label: "Cancelling…".to_owned(),
+            escape_hint: None,
+            escape_hint_label: "Press Esc to cancel".to_owned(),
+            elapsed_label: "Elapsed: {} s".to_owned(),
+            elapsed_format: ElapsedFormat::Seconds,
+            elapsed_formatter: None,
+            secondary_viewports: ViewportCoverage::Full,
+            stale_threshold: Duration::from_millis(500),
+            min_display_time: Duration::ZERO,
+            timeout: None,
+            timed_out: false,
+            show_after: Duration::ZERO,
+            milestones: Vec::new(),
+            next_milestone: 0,
+            reached_milestones: Vec::new(),
+
+            #[cfg(feature = "debug-tools")]
+            show_frame_stats: false,
+            #[cfg(feature = "debug-tools")]
+            frame_times: egui::util::History::new(0..300, 1.0),
+
+            #[cfg(feature = "frame-capture")]
+            freeze_background: false,
+            #[cfg(feature = "frame-capture")]
+            desaturate_background: false,
+            #[cfg(feature = "frame-capture")]
+            frame_capture: FrameCapture::default(),
+
+            plugin_link: PluginLink::new(),
+            handle: OwnedHandle::new(),
+            session: 0,
+            sources: Vec::new(),
+            progress_source: None,
+            on_visibility: None,
+            custom_spinner: None,
+            on_open: None,
+            on_close: None,
+            lifecycle_open: false,
+            #[cfg(feature = "testing")]
+            test_clock: TestClock::default(),
+        }
+    }
+
+    /// Sets the ID of the spinner.
+    pub fn id(mut self, id: impl Into<egui::Id>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    /// Sets the layer order the modal is painted on.
+    ///
+    /// The modal is only painted above, and only blocks input to, layers with the same
+    /// or a lower order. Defaults to `egui::Order::Foreground`, which covers all windows
+    /// and most popups.
+    pub const fn order(mut self, order: egui::Order) -> Self {
+        self.order = order;
+        self
+    }
+
+    /// If the spinner should be displayed in a modal that blocks the input.
+    ///
+    /// If false, the spinner is displayed together with the progress and the elapsed time in
+    /// a corner of the screen, without dimming the screen or blocking the input. This allows
+    /// using the same spinner for blocking and non-blocking tasks. An error reported with
+    /// [`ModalSpinner::fail`] is still displayed in the modal. Defaults to true.
+    pub const fn modal(mut self, modal: bool) -> Self {
+        self.modal = modal;
+        self
+    }
+
+    /// Sets the corner of the screen the spinner is displayed in while it does not block the
+    /// input, see [`ModalSpinner::modal`] and [`ModalSpinner::show_background_button`].
+    /// Defaults to `egui::Align2::RIGHT_BOTTOM`.
+    pub const fn indicator_corner(mut self, corner: egui::Align2) -> Self {
+        self.indicator_corner = corner;
+        self
+    }
+
+    /// Sets the visual configuration of the overlay.
+    ///
+    /// This replaces all values previously set by other style methods,
+    /// like [`ModalSpinner::fill_color`].
+    pub fn overlay_style(mut self, style: OverlayStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Sets the fill color of the modal background.
+    pub fn fill_color(mut self, color: impl Into<egui::Color32>) -> Self {
+        self.style.fill_color = Some(color.into());
+        self
+    }
+
+    /// Sets the corner rounding of the modal background.
+    ///
+    /// This is useful when the screen area covered by the modal has rounded corners itself,
+    /// for example when the application window has no decorations.
+    pub fn rounding(mut self, rounding: impl Into<egui::Rounding>) -> Self {
+        self.style.rounding = rounding.into();
+        self
+    }
+
+    /// If the modal should fade in.
+    pub const fn fade_in(mut self, fade_in: bool) -> Self {
+        self.fade_in = fade_in;
+        self
+    }
+
+    /// If the modal should fade out.
+    pub const fn fade_out(mut self, fade_out: bool) -> Self {
+        self.fade_out = fade_out;
+        self
+    }
+
+    /// Sets the size of the spinner.
+    ///
+    /// The size is ignored if it is not a finite number greater than zero.
+    /// Use [`ModalSpinner::try_spinner_size`] to detect invalid sizes.
+    pub const fn spinner_size(mut self, size: f32) -> Self {
+        if is_positive_finite(size) {
+            self.style.spinner_size = Some(size);
+        }
+
+        self
+    }
+
+    /// Sets the size of the spinner.
+    ///
+    /// Returns an error if the size is not a finite number greater than zero.
+    pub fn try_spinner_size(self, size: f32) -> Result<Self, ConfigError> {
+        if !is_positive_finite(size) {
+            return Err(ConfigError::InvalidSize(size));
+        }
+
+        Ok(self.spinner_size(size))
+    }
+
+    /// Sets the animation of the spinner. Defaults to [`SpinnerStyle::Circle`],
+    /// the default egui spinner.
+    pub const fn spinner_style(mut self, spinner_style: SpinnerStyle) -> Self {
+        self.style.spinner_style = spinner_style;
+        self
+    }
+
+    /// Sets a widget that is displayed instead of the default spinner.
+    ///
+    /// The widget is also displayed in the indicator shown while the task runs in the
+    /// background. See [`SpinnerWidget`] for more information.
+    pub fn custom_spinner(mut self, widget: impl SpinnerWidget + 'static) -> Self {
+        self.custom_spinner = Some(CustomSpinner::new(widget));
+        self
+    }
+
+    /// Sets the vertical space between the elements stacked in the modal, like the spinner,
+    /// the labels and the additional content.
+    ///
+    /// The spacing is also used as the item spacing of the additional content. If not set,
+    /// the item spacing of the current egui style is used. The spacing is ignored if it is
+    /// not a finite number greater than or equal to zero.
+    pub const fn item_spacing(mut self, spacing: f32) -> Self {
+        if spacing.is_finite() && spacing >= 0.0 {
+            self.style.item_spacing = Some(spacing);
+        }
+
+        self
+    }
+
+    /// Sets the color of the spinner.
+    pub fn spinner_color(mut self, color: impl Into<egui::Color32>) -> Self {
+        self.style.spinner_color = Some(color.into());
+        self
+    }
+
+    /// If the accent color of the egui visuals should be used for the spinner.
+    ///
+    /// The accent color is the fill color of selections, `visuals.selection.bg_fill`, which
+    /// is also used by the progress bar. This keeps the spinner consistent with apps that
+    /// customize the selection color. A color set with [`ModalSpinner::spinner_color`]
+    /// takes precedence.
+    pub const fn accent_from_visuals(mut self, accent_from_visuals: bool) -> Self {
+        self.style.accent_from_visuals = accent_from_visuals;
+        self
+    }
+
+    /// If the elapsed time should be displayed below the spinner.
+    pub const fn show_elapsed_time(mut self, show_elapsed_time: bool) -> Self {
+        self.show_elapsed_time = show_elapsed_time;
+        self
+    }
+
+    /// If the percentage should be displayed inside the progress bar.
+    ///
+    /// The progress bar is only displayed once a progress is set with
+    /// [`ModalSpinner::set_progress`]. Defaults to true.
+    pub const fn show_progress_percentage(mut self, show_progress_percentage: bool) -> Self {
+        self.show_progress_percentage = show_progress_percentage;
+        self
+    }
+
+    /// If the estimated remaining time should be displayed below the progress bar.
+    ///
+    /// The remaining time is estimated from the rate at which the progress set with
+    /// [`ModalSpinner::set_progress`] increases. See [`ModalSpinner::eta`] for more
+    /// information. Defaults to false.
+    pub const fn show_eta(mut self, show_eta: bool) -> Self {
+        self.show_eta = show_eta;
+        self
+    }
+
+    /// Sets the text of the remaining time label.
+    ///
+    /// The first `{}` in the text is replaced by the number of remaining seconds.
+    /// Defaults to `"Remaining: {} s"`.
+    pub fn eta_label(mut self, label: impl Into<String>) -> Self {
+        self.eta_label = label.into();
+        self
+    }
+
+    /// Sets a callback that is called every frame the modal is open or fading out,
+    /// with the rect it covers and its current opacity.
+    ///
+    /// The opacity is zero while the modal is not yet painted because of
+    /// [`ModalSpinner::show_after`], and on the last frame of the fade out.
+    /// The callback is not called while the spinner is closed.
+    pub fn on_visibility(
+        mut self,
+        callback: impl Fn(&OverlayVisibility) + Send + Sync + 'static,
+    ) -> Self {
+        self.on_visibility = Some(Callback::new(callback));
+        self
+    }
+
+    /// Sets a callback that is called when the spinner is opened.
+    ///
+    /// The callback is not called again if the spinner is opened while the modal is still
+    /// displayed, for example while it is fading out. Every call is followed by exactly one
+    /// call of the callback set with [`ModalSpinner::on_close`].
+    pub fn on_open(mut self, callback: impl Fn() + Send + Sync + 'static) -> Self {
+        self.on_open = Some(Callback::new(move |()| callback()));
+        self
+    }
+
+    /// Sets a callback that is called once the modal is gone after the spinner was closed.
+    ///
+    /// Unlike [`ModalSpinner::close`], this waits for the fade out animation to complete,
+    /// so it can be used to resume work that should not happen while the modal is displayed.
+    /// The callback is called during the first update after the modal disappeared.
+    pub fn on_close(mut self, callback: impl Fn() + Send + Sync + 'static) -> Self {
+        self.on_close = Some(Callback::new(move |()| callback()));
+        self
+    }
+
+    /// Sets a callback that is called when a worker thread requests a repaint, in addition to
+    /// requesting the repaint from the egui context.
+    ///
+    /// This is the case when a [`SpinnerHandle`] is used, or when the closure passed to
+    /// [`ModalSpinner::show_while`] finished. Integrations that don't wake up their event loop
+    /// when egui requests a repaint from another thread can use this to signal it themselves.
+    /// The callback is shared by all handles of the spinner and can only be set once,
+    /// further callbacks are ignored. This way, requesting a repaint never blocks the worker.
+    pub fn on_repaint_request(self, callback: impl Fn() + Send + Sync + 'static) -> Self {
+        self.handle
+            .set_repaint_callback(Callback::new(move |()| callback()));
+        self
+    }
+
+    /// Sets a progress value the spinner reads every frame while it is open.
+    ///
+    /// A worker thread can update a clone of the value without channels or locking.
+    /// See [`SpinnerProgress`] for more information.
+    pub fn progress_source(mut self, progress: SpinnerProgress) -> Self {
+        self.progress_source = Some(progress);
+        self
+    }
+
+    /// If a button to cancel the task should be displayed below the spinner.
+    ///
+    /// When the button is clicked, [`ModalSpinner::cancel_requested`] and
+    /// [`SpinnerHandle::is_cancel_requested`] return true, so the application can abort the
+    /// task. The spinner stays open until it is closed by the application, e.g. once the task
+    /// was aborted, or until a worker calls [`SpinnerHandle::acknowledge_cancel`].
+    /// Meanwhile, the button displays the text set with [`ModalSpinner::cancelling_label`].
+    /// Pressing the escape key has the same effect as clicking the button.
+    /// Defaults to false.
+    pub const fn show_cancel_button(mut self, show_cancel_button: bool) -> Self {
+        self.show_cancel_button = show_cancel_button;
+        self
+    }
+
+    /// If a button to continue the task in the background should be displayed below the
+    /// spinner.
+    ///
+    /// When the button is clicked, the modal no longer blocks the input and the spinner is
+    /// displayed in a corner of the screen instead, together with the progress and the
+    /// elapsed time, like with [`ModalSpinner::modal`] disabled. This way, the user can
+    /// continue working during long tasks. See [`ModalSpinner::is_in_background`].
+    /// Defaults to false.
+    pub const fn show_background_button(mut self, show_background_button: bool) -> Self {
+        self.show_background_button = show_background_button;
+        self
+    }
+
+    /// Sets the text of the button to continue the task in the background.
+    /// Defaults to `"Run in background"`.
+    pub fn background_label(mut self, label: impl Into<String>) -> Self {
+        self.background_label = label.into();
+        self
+    }
+
+    /// Sets the text of the cancel button. Defaults to `"Cancel"`.
+    pub fn cancel_label(mut self, label: impl Into<String>) -> Self {
+        self.cancel_label = label.into();
+        self
+    }
+
+    /// Displays a hint below the cancel button after the given delay, telling the user that
+    /// the task can be cancelled using the escape key.
+    ///
+    /// The hint is only displayed if the cancel button is enabled, see
+    /// [`ModalSpinner::show_cancel_button`], and disappears once the task is being cancelled.
+    /// By default, the hint is not displayed.
+    pub const fn escape_hint(mut self, delay: Duration) -> Self {
+        self.escape_hint = Some(delay);
+        self
+    }
+
+    /// Sets the text of the hint to cancel using the escape key.
+    /// Defaults to `"Press Esc to cancel"`. See [`ModalSpinner::escape_hint`].
+    pub fn escape_hint_label(mut self, label: impl Into<String>) -> Self {
+        self.escape_hint_label = label.into();
+        self
+    }
+
+    /// If panics of the closure passed to [`ModalSpinner::update_with_content`] or
+    /// [`ModalSpinner::update_with_spinner_ctx`] should be caught. Defaults to true.
+    ///
+    /// When the content panics, the panic is logged and a note is displayed in the modal
+    /// instead of unwinding through egui, which would abort the whole frame. The message of
+    /// the panic can be retrieved using [`ModalSpinner::take_content_panic`] to report it.
+    /// Disable this to let the panic unwind, e.g. in tests.
+    pub const fn catch_content_panics(mut self, catch_content_panics: bool) -> Self {
+        self.catch_content_panics = catch_content_panics;
+        self
+    }
+
+    /// Sets the note displayed in the modal when the content panicked.
+    /// Defaults to `"The content could not be displayed"`.
+    /// See [`ModalSpinner::catch_content_panics`].
+    pub fn content_panic_label(mut self, label: impl Into<String>) -> Self {
+        self.content_panic_label = label.into();
+        self
+    }
+
+    /// Sets the text of the button to retry the task in the error state.
+    /// Defaults to `"Retry"`. See [`ModalSpinner::fail`].
+    pub fn retry_label(mut self, label: impl Into<String>) -> Self {
+        self.retry_label = label.into();
+        self
+    }
+
+    /// Sets the text of the button to dismiss the error in the error state.
+    /// Defaults to `"Dismiss"`. See [`ModalSpinner::fail`].
+    pub fn dismiss_label(mut self, label: impl Into<String>) -> Self {
+        self.dismiss_label = label.into();
+        self
+    }
+
+    /// If a summary of the failed items of a batch operation is displayed when the spinner is
+    /// closed. Defaults to true.
+    ///
+    /// If items were marked as failed since the spinner was opened, closing the spinner
+    /// enters the error state instead, displaying the number of failures and an expandable
+    /// list of the failed items. The failures can be retrieved using
+    /// [`ModalSpinner::failures`], also after the spinner was closed.
+    pub const fn failure_summary(mut self, failure_summary: bool) -> Self {
+        self.failure_summary = failure_summary;
+        self
+    }
+
+    /// Sets the text of the failure summary. The first `{}` is replaced by the number of
+    /// failed items.
+    /// Defaults to `"{} items failed"`. See [`ModalSpinner::failure_summary`].
+    pub fn failure_label(mut self, label: impl Into<String>) -> Self {
+        self.failure_label = label.into();
+        self
+    }
+
+    /// Sets the text of the header that expands the list of failed items.
+    /// Defaults to `"Details"`. See [`ModalSpinner::failure_summary`].
+    pub fn failure_details_label(mut self, label: impl Into<String>) -> Self {
+        self.failure_details_label = label.into();
+        self
+    }
+
+    /// Sets the text of the cancel button after it was clicked, while the task is being
+    /// cancelled. Defaults to `"Cancelling…"`.
+    pub fn cancelling_label(mut self, label: impl Into<String>) -> Self {
+        self.cancelling_label = label.into();
+        self
+    }
+
+    /// Sets the text of the elapsed time label.
+    ///
+    /// The first `{}` in the text is replaced by the number of elapsed seconds,
+    /// allowing the number to be placed anywhere inside a translated text.
+    /// Defaults to `"Elapsed: {} s"`.
+    pub fn elapsed_label(mut self, label: impl Into<String>) -> Self {
+        self.elapsed_label = label.into();
+        self
+    }
+
+    /// Sets the format of the elapsed time that replaces the first `{}` in the text set with
+    /// [`ModalSpinner::elapsed_label`].
+    ///
+    /// For example, use [`ElapsedFormat::MinutesSeconds`] together with the label
+    /// `"Elapsed: {}"` to display `"Elapsed: 03:57"`. Defaults to [`ElapsedFormat::Seconds`].
+    pub const fn elapsed_format(mut self, format: ElapsedFormat) -> Self {
+        self.elapsed_format = format;
+        self
+    }
+
+    /// Sets a closure that formats the elapsed time label from the elapsed time.
+    ///
+    /// This allows full control over the label, for example to display `"00:05"`.
+    /// If set, the text set with [`ModalSpinner::elapsed_label`] is no longer used.
+    ///
+    /// # Example
+    /// ```rust
+    /// use egui_modal_spinner::ModalSpinner;
+    ///
+    /// let spinner = ModalSpinner::new().elapsed_time_formatter(|elapsed| {
+    ///     let seconds = elapsed.as_secs();
+    ///     format!("{:02}:{:02}", seconds / 60, seconds % 60)
+    /// });
+    /// ```
+    pub fn elapsed_time_formatter(
+        mut self,
+        formatter: impl Fn(Duration) -> String + Send + Sync + 'static,
+    ) -> Self {
+        self.elapsed_formatter = Some(Callback::new(move |elapsed: &Duration| formatter(*elapsed)));
+        self
+    }
+
+    /// Sets how the modal is displayed when the spinner is updated inside a viewport other
+    /// than the root viewport, for example an immediate viewport on another monitor.
+    ///
+    /// With [`ViewportCoverage::DimOnly`], the secondary viewports only display the dimmed
+    /// background to block their input, while the spinner itself is only displayed inside
+    /// the root viewport. Defaults to [`ViewportCoverage::Full`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use egui_modal_spinner::{ModalSpinner, ViewportCoverage};
+    ///
+    /// let spinner = ModalSpinner::new().secondary_viewports(ViewportCoverage::DimOnly);
+    /// ```
+    pub const fn secondary_viewports(mut self, coverage: ViewportCoverage) -> Self {
+        self.secondary_viewports = coverage;
+        self
+    }
+
+    /// Sets the time after which the open spinner is considered stale if it was not updated.
+    ///
+    /// See [`ModalSpinner::is_stale`] for more information. Defaults to 500 ms.
+    pub const fn stale_threshold(mut self, threshold: Duration) -> Self {
+        self.stale_threshold = threshold;
+        self
+    }
+
+    /// Sets the elapsed times at which a milestone is reported while the spinner is open.
+    ///
+    /// This allows the application to escalate long running tasks, for example by logging
+    /// them or offering to cancel them, without running its own timers.
+    /// See [`ModalSpinner::take_reached_milestones`] for more information.
+    ///
+    /// # Example
+    /// ```rust
+    /// use std::time::Duration;
+    ///
+    /// use egui_modal_spinner::ModalSpinner;
+    ///
+    /// let spinner = ModalSpinner::new().milestones([
+    ///     Duration::from_secs(5),
+    ///     Duration::from_secs(30),
+    ///     Duration::from_secs(120),
+    /// ]);
+    /// ```
+    pub fn milestones(mut self, milestones: impl IntoIterator<Item = Duration>) -> Self {
+        self.milestones = milestones.into_iter().collect();
+        self.milestones.sort_unstable();
+        self
+    }
+
+    /// Sets the minimum time the spinner stays open after it was opened.
+    ///
+    /// If [`ModalSpinner::close`] is called earlier, the spinner stays open until the minimum
+    /// time elapsed and is then closed automatically. This avoids a short flash of the modal
+    /// for tasks that finish quickly. Defaults to zero.
+    pub const fn min_display_time(mut self, min_display_time: Duration) -> Self {
+        self.min_display_time = min_display_time;
+        self
+    }
+
+    /// Sets the time after which the open spinner is closed automatically.
+    ///
+    /// This makes sure that a task that never finishes, like a hung worker thread, does not
+    /// block the input of the application forever. Use [`ModalSpinner::timed_out`] to check
+    /// if the spinner was closed because of the timeout. Disabled by default.
+    pub const fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the time the result is displayed when the spinner is closed using
+    /// [`ModalSpinner::close_with_success`] or [`ModalSpinner::close_with_error`].
+    ///
+    /// The modal starts fading out after this time. Defaults to 600 milliseconds.
+    pub const fn completion_duration(mut self, duration: Duration) -> Self {
+        self.completion_duration = duration;
+        self
+    }
+
+    /// Sets the time the spinner has to be open before the modal is painted.
+    ///
+    /// The input to the UI beneath the modal is blocked from the moment the spinner is opened.
+    /// The dimmed background and the spinner are only painted once the spinner has been open
+    /// for the given time, which avoids visual noise for tasks that usually finish quickly.
+    /// If the spinner closes before that, it is not shown at all. Defaults to zero.
+    pub const fn show_after(mut self, delay: Duration) -> Self {
+        self.show_after = delay;
+        self
+    }
+
+    /// If the frame rate and frame time should be displayed in the top left corner
+    /// of the modal.
+    ///
+    /// This is useful to verify that the background task is not starving the UI thread.
+    #[cfg(feature = "debug-tools")]
+    pub const fn show_frame_stats(mut self, show_frame_stats: bool) -> Self {
+        self.show_frame_stats = show_frame_stats;
+        self
+    }
+
+    /// If the last rendered frame should be displayed frozen beneath the modal.
+    ///
+    /// When the spinner opens, a screenshot of the application is requested from the
+    /// integration, e.g. eframe. Once the screenshot is available, it is displayed beneath
+    /// the modal background and [`ModalSpinner::is_background_frozen`] returns true.
+    /// The application can then skip rendering its UI beneath the modal until the
+    /// spinner is closed, which saves the cost of expensive panels during long tasks.
+    ///
+    /// The modal is fully transparent in the frame the screenshot is taken, so that the
+    /// screenshot only contains the UI beneath the modal. The captured frame is discarded
+    /// if the size of the screen changes while the spinner is open.
+    #[cfg(feature = "frame-capture")]
+    pub const fn freeze_background(mut self, freeze_background: bool) -> Self {
+        self.freeze_background = freeze_background;
+        self
+    }
+
+    /// If the frozen background should be displayed in grayscale, to make it more obvious
+    /// that the application is busy and does not respond to input.
+    ///
+    /// The captured frame is converted once when the screenshot arrives, so this does not
+    /// add any cost to the following frames. Only has an effect if
+    /// [`ModalSpinner::freeze_background`] is enabled.
+    #[cfg(feature = "frame-capture")]
+    pub const fn desaturate_background(mut self, desaturate_background: bool) -> Self {
+        self.desaturate_background = desaturate_background;
+        self
+    }
+}
+
+/// Getter and setter
+impl ModalSpinner {
+    /// Gets the current state of the spinner.
+    pub const fn state(&self) -> &SpinnerState {
+        &self.state
+    }
+
+    /// Gets the visual configuration of the overlay.
+    pub const fn style(&self) -> &OverlayStyle {
+        &self.style
+    }
+
+    /// Gets a mutable reference to the visual configuration of the overlay.
+    pub const fn style_mut(&mut self) -> &mut OverlayStyle {
+        &mut self.style
+    }
+
+    /// Replaces the visual configuration of the overlay.
+    /// The new style is used starting with the next update.
+    pub fn set_style(&mut self, style: OverlayStyle) {
+        self.style = style;
+    }
+
+    /// Gets the ID the modal area of the spinner uses.
+    ///
+    /// This is the ID set with [`ModalSpinner::id`], or the default ID derived from the
+    /// location the spinner was created at. See [`ModalSpinner::new`] for more information.
+    pub fn effective_id(&self) -> egui::Id {
+        self.id.unwrap_or(self.default_id)
+    }
+
+    /// Gets the layer the modal is painted on.
+    ///
+    /// This can be used to order other layers relative to the modal, for example by moving
+    /// a window above it with `egui::Context::move_to_top` after updating the spinner.
+    pub fn layer_id(&self) -> egui::LayerId {
+        egui::LayerId::new(self.order, self.effective_id())
+    }
+
+    /// Gets a handle to control the spinner from worker threads.
+    ///
+    /// All handles of a spinner control the same spinner. A cloned spinner is a separate
+    /// spinner with its own handles. See [`SpinnerHandle`] for more information.
+    pub fn handle(&self) -> SpinnerHandle {
+        self.handle.share()
+    }
+
+    /// Sets the progress value the spinner reads every frame while it is open.
+    ///
+    /// While the value has a progress, it replaces the progress set with
+    /// [`ModalSpinner::set_progress`]. The value is cleared when the spinner is opened.
+    /// See [`SpinnerProgress`] for more information.
+    pub fn set_progress_source(&mut self, progress: SpinnerProgress) {
+        self.progress_source = Some(progress);
+    }
+
+    /// Checks if the user requested to cancel the task using the cancel button.
+    ///
+    /// This is reset when the spinner is opened. See [`ModalSpinner::show_cancel_button`].
+    pub const fn cancel_requested(&self) -> bool {
+        self.cancel_requested
+    }
+
+    /// Checks if a worker acknowledged the cancel request using
+    /// [`SpinnerHandle::acknowledge_cancel`], which also closes the spinner.
+    ///
+    /// This is reset when the spinner is opened.
+    pub const fn cancel_acknowledged(&self) -> bool {
+        self.cancel_acknowledged
+    }
+
+    /// Gets the progress of the task between 0.0 and 1.0.
+    /// Returns None if no progress was set since the spinner was opened.
+    pub const fn progress(&self) -> Option<f32> {
+        self.progress
+    }
+
+    /// Sets the progress of the task between 0.0 and 1.0.
+    ///
+    /// Once a progress is set, a progress bar is displayed below the spinner.
+    /// The value is clamped to the range 0.0 to 1.0 and ignored if it is not a number.
+    /// The progress is reset when the spinner is opened.
+    pub const fn set_progress(&mut self, progress: f32) {
+        if !progress.is_nan() {
+            self.progress = Some(progress.clamp(0.0, 1.0));
+        }
+    }
+
+    /// Removes the progress, so that the progress bar is no longer displayed.
+    pub const fn clear_progress(&mut self) {
+        self.progress = None;
+    }
+
+    /// Gets the estimated time remaining until the progress reaches 1.0.
+    ///
+    /// The estimate is based on a smoothed rate of the progress, measured while the spinner
+    /// is updated. Returns None if no progress is set, or if the progress did not increase
+    /// since it was first set.
+    pub fn eta(&self) -> Option<Duration> {
+        let progress = self.progress?;
+        let rate = self
+            .progress_rate
+            .filter(|rate| is_positive_finite(*rate))?;
+
+        Duration::try_from_secs_f32((1.0 - progress) / rate).ok()
+    }
+
+    /// Gets the status message displayed beneath the spinner, if set.
+    pub fn message(&self) -> Option<&str> {
+        self.message.as_deref()
+    }
+
+    /// Sets a status message that is displayed beneath the spinner, e.g. "Loading cats...".
+    ///
+    /// The message is removed when the spinner is opened. Use [`SpinnerHandle::set_message`]
+    /// to set the message from a worker thread.
+    pub fn set_message(&mut self, message: impl Into<String>) {
+        self.message = Some(message.into());
+    }
+
+    /// Removes the status message displayed beneath the spinner.
+    pub fn clear_message(&mut self) {
+        self.message = None;
+    }
+
+    /// Sets the steps of the task, which are displayed as a list beneath the spinner.
+    ///
+    /// Each step is displayed with an icon reflecting its [`StepState`]. All steps start
+    /// pending, use [`ModalSpinner::complete_step`] to mark them as completed. The first step
+    /// that was not completed is displayed as active. The steps are kept when the spinner is
+    /// opened again, so set them again for every task.
+    ///
+    /// # Example
+    /// ```rust
+    /// use egui_modal_spinner::ModalSpinner;
+    ///
+    /// let mut spinner = ModalSpinner::new();
+    ///
+    /// spinner.open();
+    /// spinner.set_steps(["Downloading", "Verifying", "Installing"]);
+    ///
+    /// // When the download finished
+    /// spinner.complete_step(0);
+    /// ```
+    pub fn set_steps(&mut self, steps: impl IntoIterator<Item = impl Into<String>>) {
+        self.steps = steps
+            .into_iter()
+            .map(|label| Step {
+                label: label.into(),
+                completed: false,
+            })
+            .collect();
+    }
+
+    /// Marks the step at the given index as completed.
+    /// Indices of steps that don't exist are ignored.
+    pub fn complete_step(&mut self, index: usize) {
+        if let Some(step) = self.steps.get_mut(index) {
+            step.completed = true;
+        }
+    }
+
+    /// Gets the state of the step at the given index, or None if the step does not exist.
+    pub fn step_state(&self, index: usize) -> Option<StepState> {
+        steps::step_states(&self.steps).nth(index)
+    }
+
+    /// Removes all steps, so that the list of steps is no longer displayed.
+    pub fn clear_steps(&mut self) {
+        self.steps.clear();
+    }
+
+    /// Sets a graph of dependent tasks, whose active tasks are displayed as a list beneath
+    /// the spinner.
+    ///
+    /// While a graph is set, it is displayed instead of the steps set with
+    /// [`ModalSpinner::set_steps`], and the progress bar displays the completed share of its
+    /// tasks. Update the graph using [`ModalSpinner::task_graph_mut`] as the tasks are started
+    /// and completed. The graph is kept when the spinner is opened again.
+    /// See [`TaskGraph`] for more information.
+    pub fn set_task_graph(&mut self, graph: TaskGraph) {
+        self.task_graph = Some(graph);
+    }
+
+    /// Gets the graph of dependent tasks, if set.
+    pub const fn task_graph(&self) -> Option<&TaskGraph> {
+        self.task_graph.as_ref()
+    }
+
+    /// Gets a mutable reference to the graph of dependent tasks, if set.
+    pub const fn task_graph_mut(&mut self) -> Option<&mut TaskGraph> {
+        self.task_graph.as_mut()
+    }
+
+    /// Removes the graph of dependent tasks, so that it is no longer displayed.
+    pub fn clear_task_graph(&mut self) {
+        self.task_graph = None;
+    }
+
+    /// Sets the number of items of a batch operation, which are displayed as a mini-map of
+    /// cells below the progress bar.
+    ///
+    /// All items start pending. Report the processed items by their index using
+    /// [`ModalSpinner::complete_item`] and [`ModalSpinner::fail_item`], or from worker threads
+    /// using the handle of the spinner. The cells are colored by the [`ItemState`] of their
+    /// items, so the map shows which parts of e.g. a tiled image are already processed.
+    /// For very large batches, every cell represents a range of items.
+    /// The items are kept when the spinner is opened again, so set them again for every task.
+    ///
+    /// # Example
+    /// ```rust
+    /// use egui_modal_spinner::ModalSpinner;
+    ///
+    /// let mut spinner = ModalSpinner::new();
+    /// let handle = spinner.handle();
+    ///
+    /// spinner.open();
+    /// spinner.set_item_count(64);
+    ///
+    /// std::thread::spawn(move || {
+    ///     for tile in 0..64 {
+    ///         // Process the tile
+    ///         handle.complete_item(tile);
+    ///     }
+    /// });
+    /// ```
+    pub fn set_item_count(&mut self, count: usize) {
+        self.items = vec![ItemState::Pending; count];
+    }
+
+    /// Marks the item at the given index as completed.
+    /// Indices of items that don't exist are ignored.
+    pub fn complete_item(&mut self, index: usize) {
+        self.set_item_state(index, ItemState::Completed);
+    }
+
+    /// Marks the item at the given index as failed.
+    /// Like every failure, it is recorded even if the item does not exist.
+    pub fn fail_item(&mut self, index: usize) {
+        self.record_failure(index, None);
+    }
+
+    /// Marks the item at the given index as failed because of the given reason.
+    ///
+    /// The failures are collected while the spinner is open and displayed in a summary when
+    /// it is closed, see [`ModalSpinner::failure_summary`].
+    pub fn fail_item_with_reason(&mut self, index: usize, reason: impl Into<String>) {
+        self.record_failure(index, Some(reason.into()));
+    }
+
+    /// Gets the items that failed since the spinner was last opened,
+    /// in the order they were reported.
+    pub fn failures(&self) -> &[ItemFailure] {
+        &self.failures
+    }
+
+    /// Takes the items that failed since the spinner was last opened.
+    pub fn take_failures(&mut self) -> Vec<ItemFailure> {
+        std::mem::take(&mut self.failures)
+    }
+
+    /// Gets the state of the item at the given index, or None if the item does not exist.
+    pub fn item_state(&self, index: usize) -> Option<ItemState> {
+        self.items.get(index).copied()
+    }
+
+    /// Removes all items, so that the mini-map is no longer displayed.
+    pub fn clear_items(&mut self) {
+        self.items.clear();
+    }
+
+    /// Takes the milestones that were reached since this method was last called.
+    ///
+    /// The milestones are checked when the spinner is updated while it is open, so this
+    /// should be called after the update method of the spinner. They are reset when the
+    /// spinner is opened. See [`ModalSpinner::milestones`] for more information.
+    pub fn take_reached_milestones(&mut self) -> Vec<Duration> {
+        std::mem::take(&mut self.reached_milestones)
+    }
+
+    /// Checks if the spinner was closed automatically because the configured
+    /// [`ModalSpinner::timeout`] elapsed.
+    ///
+    /// This is reset when the spinner is opened again.
+    pub const fn timed_out(&self) -> bool {
+        self.timed_out
+    }
+
+    /// Checks if painting the modal was skipped during the last update because the screen
+    /// rect was empty, for example while the window is minimized.
+    ///
+    /// The spinner keeps running while it is not painted, so the elapsed time, the timeout
+    /// and the fade animation continue. The modal is painted again as soon as the screen
+    /// rect is restored.
+    pub const fn rendering_skipped(&self) -> bool {
+        self.rendering_skipped
+    }
+
+    /// Takes the message of the last panic of the content closure, if it panicked since this
+    /// method was last called. See [`ModalSpinner::catch_content_panics`].
+    pub const fn take_content_panic(&mut self) -> Option<String> {
+        self.content_panic.take()
+    }
+
+    /// Checks if the content closure panicked during the last update.
+    /// See [`ModalSpinner::catch_content_panics`].
+    pub const fn content_panicked(&self) -> bool {
+        self.content_panicked
+    }
+
+    /// Checks if the spinner is currently open.
+    /// This is also the case while the spinner displays an error, see [`ModalSpinner::fail`].
+    pub const fn is_open(&self) -> bool {
+        matches!(self.state, SpinnerState::Open | SpinnerState::Error)
+    }
+
+    /// Checks if the user moved the task to the background using the button enabled with
+    /// [`ModalSpinner::show_background_button`].
+    ///
+    /// While in the background, the spinner is still open, but does not block the input.
+    /// This is reset when the spinner is opened.
+    pub const fn is_in_background(&self) -> bool {
+        self.in_background
+    }
+
+    /// Gets the error displayed by the spinner, if it is in the error state.
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    /// Takes the button of the error state the user clicked since the last call, if any.
+    ///
+    /// See [`ModalSpinner::fail`] for more information.
+    pub const fn take_error_action(&mut self) -> Option<ErrorAction> {
+        self.error_action.take()
+    }
+
+    /// Checks if the UI beneath the spinner should be disabled.
+    ///
+    /// This is the case while the spinner is open or still fading out.
+    /// The modal already suppresses user input, this can be used to additionally
+    /// disable the UI beneath the modal, for example with [`ModalSpinner::add_enabled_ui`].
+    pub const fn should_disable_ui(&self) -> bool {
+        (self.is_open() && !self.is_non_modal()) || self.fading_out
+    }
+
+    /// Checks if the open spinner is displayed in a corner of the screen without blocking
+    /// the input, instead of in the modal.
+    const fn is_non_modal(&self) -> bool {
+        matches!(self.state, SpinnerState::Open) && (self.in_background || !self.modal)
+    }
+
+    /// Gets the time elapsed since the spinner was last opened.
+    pub fn elapsed(&self) -> Duration {
+        self.now().saturating_duration_since(self.timestamp)
+    }
+
+    /// Checks if the spinner is open but was not updated for longer than the
+    /// configured [`ModalSpinner::stale_threshold`].
+    ///
+    /// A stale spinner usually means that the update call site is inside a branch that
+    /// stopped executing, leaving the spinner open without being visible.
+    /// Note that this is also the case if the application is not rendering frames at all,
+    /// e.g. while the window is minimized.
+    pub fn is_stale(&self) -> bool {
+        self.state == SpinnerState::Open && self.time_since_update() > self.stale_threshold
+    }
+
+    /// Checks if the spinner is open and displays a captured frame beneath the modal.
+    ///
+    /// While this returns true, the UI beneath the modal is covered by the captured frame
+    /// and does not need to be rendered. See [`ModalSpinner::freeze_background`].
+    #[cfg(feature = "frame-capture")]
+    pub fn is_background_frozen(&self) -> bool {
+        self.is_open() && self.frame_capture.texture().is_some()
+    }
+
+    /// Gets the link to the registration of the spinner with the [`ModalSpinnerPlugin`].
+    pub(crate) const fn plugin_link(&self) -> &PluginLink {
+        &self.plugin_link
+    }
+
+    /// Creates the view the [`ModalSpinnerPlugin`] renders in frames the spinner is not
+    /// updated. The view only contains what is needed to render the modal, so the requests
+    /// of the handles stay pending for the spinner owned by the application.
+    pub(crate) fn plugin_view(&self) -> PluginView {
+        PluginView {
+            order: self.order,
+            style: self.style.clone(),
+            failed: self.state == SpinnerState::Error,
+            progress: self.progress,
+            show_progress_percentage: self.show_progress_percentage,
+            custom_spinner: self.custom_spinner.clone(),
+            started: self.timestamp,
+            handle: self.handle.share(),
+        }
+    }
+}
+
+/// Implementation methods
+impl ModalSpinner {
+    /// Opens the spinner.
+    pub fn open(&mut self) {
+        // When opened while fading out, the modal fades in again from its current opacity.
+        // Opening an open spinner again keeps its opacity.
+        if !self.is_open() && !self.fading_out {
+            self.revealed = false;
+            self.reset_fade();
+        }
+
+        self.state = SpinnerState::Open;
+        self.fading_out = false;
+        self.close_pending = false;
+        self.timed_out = false;
+        self.pending_open = true;
+        self.timestamp = self.now();
+        self.last_update = None;
+        self.cancel_requested = false;
+        self.cancel_acknowledged = false;
+        self.handle.reset_cancel();
+        self.progress = None;
+        self.progress_sample = None;
+        self.progress_rate = None;
+        self.message = None;
+        self.next_milestone = 0;
+        self.reached_milestones.clear();
+        self.completion = None;
+        self.completion_started = None;
+        self.error = None;
+        self.error_action = None;
+        self.in_background = false;
+        self.failures.clear();
+        self.plugin_link.set_open(true);
+        self.session = self.handle.begin_session();
+
+        if let Some(progress) = &self.progress_source {
+            progress.clear();
+        }
+
+        if !self.lifecycle_open {
+            self.lifecycle_open = true;
+
+            if let Some(callback) = &self.on_open {
+                callback.call(&());
+            }
+        }
+    }
+
+    /// Opens the spinner and returns a guard that closes it when it is dropped.
+    ///
+    /// This ensures that the spinner can not stay open by accident, for example because of an
+    /// early return. See [`SpinnerGuard`] for more information.
+    pub fn open_guard(&mut self) -> SpinnerGuard {
+        self.open();
+        SpinnerGuard::new(self.handle.share(), self.session)
+    }
+
+    /// Opens the spinner on behalf of the given source, like a background task.
+    ///
+    /// The spinner stays open until all sources it was opened for were closed using
+    /// [`ModalSpinner::close_for`], so overlapping tasks don't close the spinner while
+    /// another task is still running. Opening for a source while the spinner is already open
+    /// keeps its current state, like the elapsed time and the progress.
+    /// Opening for a source that is already active has no effect.
+    ///
+    /// # Example
+    /// ```rust
+    /// use egui_modal_spinner::ModalSpinner;
+    ///
+    /// let mut spinner = ModalSpinner::new();
+    ///
+    /// spinner.open_for("db-sync");
+    /// spinner.open_for("upload");
+    ///
+    /// spinner.close_for("db-sync");
+    /// assert!(spinner.is_open());
+    ///
+    /// spinner.close_for("upload");
+    /// assert!(!spinner.is_open());
+    /// ```
+    pub fn open_for(&mut self, source: impl Into<String>) {
+        let source = source.into();
+
+        if self.sources.contains(&source) {
+            return;
+        }
+
+        if !self.is_open() {
+            self.open();
+        }
+
+        self.sources.push(source);
+    }
+
+    /// Closes the spinner on behalf of the given source.
+    ///
+    /// The spinner is only closed once no other source it was opened for with
+    /// [`ModalSpinner::open_for`] is active. Sources that are not active are ignored.
+    pub fn close_for(&mut self, source: &str) {
+        let Some(index) = self.sources.iter().position(|active| active == source) else {
+            return;
+        };
+
+        self.sources.remove(index);
+
+        if self.sources.is_empty() {
+            self.close();
+        }
+    }
+
+    /// Gets the sources the spinner is currently open for, in the order they were opened.
+    pub fn active_sources(&self) -> impl Iterator<Item = &str> {
+        self.sources.iter().map(String::as_str)
+    }
+
+    /// Closes the spinner.
+    ///
+    /// If the spinner was opened less than the configured [`ModalSpinner::min_display_time`]
+    /// ago, it stays open until the minimum display time has elapsed.
+    /// This closes the spinner regardless of the sources it was opened for using
+    /// [`ModalSpinner::open_for`], which are all discarded.
+    ///
+    /// If items failed while the spinner was open, a summary of the failures is displayed
+    /// instead, see [`ModalSpinner::failure_summary`].
+    pub fn close(&mut self) {
+        self.sources.clear();
+
+        if !self.is_open() && !self.fading_out {
+            return;
+        }
+
+        if self.show_failure_summary() {
+            return;
+        }
+
+        if self.is_open() && self.elapsed() < self.min_display_time {
+            self.close_pending = true;
+            return;
+        }
+
+        // The indicator displayed while not modal disappears without fading out.
+        let non_modal = self.is_non_modal();
+
+        self.close_pending = false;
+        self.state = SpinnerState::Closed;
+        // A spinner closed before the show delay elapsed was never visible and is
+        // closed without fading out.
+        self.fading_out =
+            self.fade_out && !non_modal && (self.revealed || self.show_after.is_zero());
+        self.plugin_link.set_open(false);
+
+        if !self.fading_out {
+            self.reset_fade();
+        }
+
+        #[cfg(feature = "frame-capture")]
+        self.frame_capture.clear();
+    }
+
+    /// Reports that the task failed, keeping the modal open to display the error.
+    ///
+    /// The spinner is replaced by an error icon and the given message, with buttons to retry
+    /// the task or to dismiss the error. The button clicked by the user is returned by
+    /// [`ModalSpinner::take_error_action`]. Dismissing the error closes the spinner.
+    /// To retry, start the task again and call [`ModalSpinner::open`].
+    ///
+    /// This has no effect if the spinner is closed.
+    ///
+    /// # Example
+    /// ```rust
+    /// use egui_modal_spinner::{ErrorAction, ModalSpinner};
+    ///
+    /// fn update(spinner: &mut ModalSpinner, ctx: &egui::Context) {
+    ///     if spinner.take_error_action() == Some(ErrorAction::Retry) {
+    ///         // Start the task again
+    ///         spinner.open();
+    ///     }
+    ///
+    ///     spinner.update(ctx);
+    /// }
+    /// ```
+    pub fn fail(&mut self, message: impl Into<String>) {
+        if !self.is_open() {
+            return;
+        }
+
+        self.state = SpinnerState::Error;
+        self.error = Some(message.into());
+        self.error_action = None;
+        self.close_pending = false;
+        self.completion = None;
+        // The error needs the attention of the user, so it is displayed in the modal again.
+        self.in_background = false;
+        // The error is displayed immediately, even if the show delay did not yet elapse.
+        self.revealed = true;
+    }
+
+    /// Closes the spinner after briefly displaying an animated checkmark instead of the spinner.
+    ///
+    /// This gives the user feedback that the task was successful, instead of the modal just
+    /// disappearing. The modal keeps blocking the input while the checkmark is displayed and
+    /// fades out afterwards. See [`ModalSpinner::completion_duration`].
+    pub fn close_with_success(&mut self) {
+        self.close_with(Completion::Success);
+    }
+
+    /// Closes the spinner after briefly displaying an animated cross instead of the spinner.
+    ///
+    /// Like [`ModalSpinner::close_with_success`], but indicates that the task failed.
+    pub fn close_with_error(&mut self) {
+        self.close_with(Completion::Failure);
+    }
+
+    fn close_with(&mut self, completion: Completion) {
+        // The result is not displayed if the modal was never visible
+        // or if the summary of the failed items is displayed instead.
+        if self.show_failure_summary() {
+            return;
+        }
+
+        if !self.is_open() || !self.revealed || self.completion_duration.is_zero() {
+            self.close();
+            return;
+        }
+
+        if self.completion.is_none() {
+            self.completion = Some(completion);
+        }
+    }
+
+    /// Opens the spinner and updates it immediately.
+    ///
+    /// Call this in the frame in which the task is triggered, e.g. when a button is clicked,
+    /// instead of only calling [`ModalSpinner::open`]. The modal then already suppresses input
+    /// and is painted in this frame, so there is no frame in which a second click on the
+    /// button could slip through. Don't call [`ModalSpinner::update`] in the same frame again.
+    pub fn open_and_update(&mut self, ctx: &egui::Context) {
+        self.open();
+        self.update(ctx);
+    }
+
+    /// Opens the spinner and runs the given closure on a background thread.
+    ///
+    /// Pass the returned receiver to [`ModalSpinner::update_with_receiver`] every frame.
+    /// It returns the result of the closure once it finished and closes the spinner.
+    /// The UI keeps repainting while the closure runs, and a repaint is requested when
+    /// it finished.
+    ///
+    /// The closure is run using `std::thread::spawn`, which is not supported on
+    /// `wasm32-unknown-unknown`. Use [`ModalSpinner::update_with_task`] on the web instead.
+    ///
+    /// # Example
+    /// ```rust
+    /// use std::sync::mpsc;
+    ///
+    /// use egui_modal_spinner::ModalSpinner;
+    ///
+    /// # fn heavy_work() -> u32 { 42 }
+    /// # fn ui(
+    /// #     ctx: &egui::Context,
+    /// #     spinner: &mut ModalSpinner,
+    /// #     receiver: &mut Option<mpsc::Receiver<u32>>,
+    /// # ) {
+    /// if receiver.is_none() {
+    ///     *receiver = Some(spinner.show_while(ctx, heavy_work));
+    /// }
+    ///
+    /// if let Some(rx) = receiver {
+    ///     if let Some(result) = spinner.update_with_receiver(ctx, rx) {
+    ///         println!("Result: {result}");
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    pub fn show_while<T, F>(&mut self, ctx: &egui::Context, work: F) -> mpsc::Receiver<T>
+    where
+        T: Send + 'static,
+        F: FnOnce() -> T + Send + 'static,
+    {
+        let (tx, rx) = mpsc::channel();
+        let handle = self.handle.share();
+        handle.set_context(ctx);
+
+        self.open();
+
+        thread::spawn(move || {
+            let _ = tx.send(work());
+            handle.request_repaint();
+        });
+
+        rx
+    }
+
+    /// Main update method of the spinner that should be called every frame if you want the
+    /// spinner to be visible.
+    ///
+    /// The modal is only displayed while the spinner is open, displays an error or fades out.
+    pub fn update(&mut self, ctx: &egui::Context) {
+        self.update_ui(ctx, |_, _| ());
+    }
+
+    /// Main update method of the spinner that should be called every frame if you want the
+    /// spinner to be visible.
+    ///
+    /// This method allows additional content to be displayed under the
+    /// spinner - or if activated - under the elapsed time.
+    /// However, note that the additional content is not taken into account when
+    /// centering the spinner. Therefore, a large amount of additional
+    /// content on the Y-axis is not recommended.
+    ///
+    /// The modal itself does not change the cursor icon. egui resets the cursor icon every
+    /// frame, so a cursor icon set by the content, like a wait cursor, is restored with the
+    /// first frame after the spinner closed, even if the content panicked.
+    ///
+    /// The modal is only displayed while the spinner is open, displays an error or fades out.
+    pub fn update_with_content(&mut self, ctx: &egui::Context, ui: impl FnOnce(&mut egui::Ui)) {
+        self.update_ui(ctx, |u, _| ui(u));
+    }
+
+    /// Main update method of the spinner that should be called every frame if you want the
+    /// spinner to be visible.
+    ///
+    /// Like [`ModalSpinner::update_with_content`], but the closure also receives the
+    /// current state of the spinner, like the elapsed time and the progress.
+    ///
+    /// The modal is only displayed while the spinner is open, displays an error or fades out.
+    pub fn update_with_spinner_ctx(
+        &mut self,
+        ctx: &egui::Context,
+        ui: impl FnOnce(&mut egui::Ui, &SpinnerCtx),
+    ) {
+        self.update_ui(ctx, ui);
+    }
+
+    /// Main update method of the spinner when executing a [`CooperativeTask`] on the UI thread.
+    ///
+    /// While the spinner is open, a slice of the task is executed before the spinner is
+    /// updated. When the task is done, the spinner is closed and the output of the task
+    /// is returned. The task is not executed while the spinner is closed.
+    ///
+    /// The progress reported by the task with [`TaskStep::Progress`] is set as the progress
+    /// of the spinner, see [`ModalSpinner::set_progress`].
+    ///
+    /// [`TaskStep::Progress`]: crate::TaskStep::Progress
+    pub fn update_with_task<T>(
+        &mut self,
+        ctx: &egui::Context,
+        task: &mut CooperativeTask<T>,
+    ) -> Option<T> {
+        let output = if self.is_open() {
+            let frame_time = ctx.input(|i| i.unstable_dt);
+
+            // The frame time reported by the integration is not trusted to be in range.
+            if let Ok(frame_time) = Duration::try_from_secs_f32(frame_time.max(0.0)) {
+                task.tune_slice_duration(frame_time);
+            }
+
+            let output = task.run_slice();
+
+            if let Some(progress) = task.progress() {
+                self.set_progress(progress);
+            }
+
+            output
+        } else {
+            None
+        };
+
+        if output.is_some() {
+            self.close();
+        } else if self.is_open() {
+            // Make sure the next slice is executed as soon as possible.
+            ctx.request_repaint();
+        }
+
+        if task.show_frame_budget {
+            let frame_time = ctx.input(|i| i.unstable_dt) * 1000.0;
+            let slice_time = task.last_slice_time().as_secs_f32() * 1000.0;
+
+            let style = self.style.clone();
+
+            self.update_ui(ctx, |ui, _| {
+                ui.add_space(ui.spacing().item_spacing.y);
+                style.ui_label(
+                    ui,
+                    format!("Task: {slice_time:.1} ms of {frame_time:.1} ms frame time"),
+                );
+            });
+        } else {
+            self.update(ctx);
+        }
+
+        output
+    }
+
+    /// Main update method of the spinner when waiting for the result of a worker thread.
+    ///
+    /// While the spinner is open, the receiver is polled every frame. The spinner is closed
+    /// when a value is received, which is then returned, or when all senders were dropped
+    /// without sending a value. The receiver is not polled while the spinner is closed.
+    ///
+    /// See [`ModalSpinner::show_while`] to run a closure on a background thread and receive
+    /// its result.
+    pub fn update_with_receiver<T>(
+        &mut self,
+        ctx: &egui::Context,
+        receiver: &mpsc::Receiver<T>,
+    ) -> Option<T> {
+        let output = if self.is_open() {
+            match receiver.try_recv() {
+                Ok(value) => Some(Some(value)),
+                Err(mpsc::TryRecvError::Disconnected) => Some(None),
+                Err(mpsc::TryRecvError::Empty) => None,
+            }
+        } else {
+            None
+        };
+
+        if output.is_some() {
+            self.close();
+        } else if self.is_open() {
+            // The worker thread can not request a repaint, so poll again in the next frame.
+            ctx.request_repaint();
+        }
+
+        self.update(ctx);
+
+        output.flatten()
+    }
+
+    /// Adds the given UI, which is disabled while [`ModalSpinner::should_disable_ui`]
+    /// returns true.
+    pub fn add_enabled_ui<R>(
+        &self,
+        ui: &mut egui::Ui,
+        add_contents: impl FnOnce(&mut egui::Ui) -> R,
+    ) -> egui::InnerResponse<R> {
+        ui.add_enabled_ui(!self.should_disable_ui(), add_contents)
+    }
+
+    /// Checks if the given response was clicked while the spinner was not active.
+    ///
+    /// Returns false if the spinner is open or fading out, or if it was active during one of
+    /// the last two passes. The hit test of egui is based on the previous pass, so the first
+    /// pass without the modal can still register clicks that were queued while the modal was
+    /// open. This protects actions guarded by the spinner from being triggered again by them.
+    /// Use [`ModalSpinner::guard_click_any`] to check against all spinners of the context.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::sync::mpsc;
+    /// # use egui_modal_spinner::ModalSpinner;
+    /// # fn heavy_work() -> u32 { 42 }
+    /// # fn ui(
+    /// #     ui: &mut egui::Ui,
+    /// #     spinner: &mut ModalSpinner,
+    /// #     receiver: &mut Option<mpsc::Receiver<u32>>,
+    /// # ) {
+    /// if spinner.guard_click(&ui.button("Run task")) {
+    ///     *receiver = Some(spinner.show_while(ui.ctx(), heavy_work));
+    /// }
+    /// # }
+    /// ```
+    pub fn guard_click(&self, response: &egui::Response) -> bool {
+        response.clicked()
+            && !self.should_disable_ui()
+            && !Self::was_recently_active(&response.ctx, self.effective_id().with(LAST_ACTIVE_ID))
+    }
+
+    /// Checks if the given response was clicked while no spinner of the context was active.
+    ///
+    /// See [`ModalSpinner::guard_click`] for more information.
+    pub fn guard_click_any(response: &egui::Response) -> bool {
+        response.clicked()
+            && !Self::was_recently_active(&response.ctx, egui::Id::new(LAST_ACTIVE_ID))
+    }
+
+    /// Creates a textual snapshot of the current state of the spinner.
+    ///
+    /// This is useful, for example, to attach the state of the overlay to an error report
+    /// when the task the spinner was opened for fails.
+    pub fn describe(&self) -> String {
+        let state = match self.state {
+            SpinnerState::Closed if self.fading_out => "Closed (fading out)",
+            SpinnerState::Closed => "Closed",
+            SpinnerState::Open => "Open",
+            SpinnerState::Error => "Error",
+        };
+
+        let progress = self
+            .progress
+            .map(|progress| format!("\nprogress: {:.0} %", progress * 100.0))
+            .unwrap_or_default();
+
+        let message = self
+            .message
+            .as_ref()
+            .map(|message| format!("\nmessage: {message}"))
+            .unwrap_or_default();
+
+        let error = self
+            .error
+            .as_ref()
+            .map(|error| format!("\nerror: {error}"))
+            .unwrap_or_default();
+
+        let cancel = if self.cancel_acknowledged {
+            "\ncancel: acknowledged"
+        } else if self.cancel_requested {
+            "\ncancel: requested"
+        } else {
+            ""
+        };
+
+        let failures = if self.failures.is_empty() {
+            String::new()
+        } else {
+            format!("\nfailures: {}", self.failures.len())
+        };
+
+        format!(
+            "state: {state}\nelapsed: {}{progress}{message}{error}{cancel}{failures}",
+            self.elapsed_format.format(self.elapsed())
+        )
+    }
+}
+
+/// Testing methods
+#[cfg(feature = "testing")]
+impl ModalSpinner {
+    /// Advances the clock of the spinner by the given time and updates the spinner once,
+    /// as if the next frame was rendered after this time.
+    ///
+    /// This allows unit testing the logic that opens and closes the spinner, including
+    /// timeouts, minimum display times and animations, without a window or a renderer and
+    /// without waiting for the time to pass. The spinner is updated with a context owned by
+    /// the spinner. Don't mix this with updates using the context of the application.
+    ///
+    /// # Example
+    /// ```rust
+    /// use std::time::Duration;
+    ///
+    /// use egui_modal_spinner::ModalSpinner;
+    ///
+    /// let mut spinner = ModalSpinner::new().timeout(Duration::from_secs(30));
+    ///
+    /// spinner.open();
+    /// spinner.advance_time(Duration::from_secs(10));
+    /// assert!(spinner.is_open());
+    ///
+    /// spinner.advance_time(Duration::from_secs(20));
+    /// assert!(spinner.timed_out());
+    /// ```
+    pub fn advance_time(&mut self, delta: Duration) {
+        self.test_clock.offset = self.test_clock.offset.saturating_add(delta);
+
+        let ctx = self
+            .test_clock
+            .ctx
+            .get_or_insert_with(egui::Context::default)
+            .clone();
+
+        let input = egui::RawInput {
+            time: Some(self.test_clock.offset.as_secs_f64()),
+            ..Default::default()
+        };
+
+        let _ = ctx.run(input, |ctx| self.update(ctx));
+    }
+
+    /// Gets the opacity of the modal between 0.0 and 1.0, as of the last update.
+    pub fn opacity(&self) -> f32 {
+        if !self.revealed || (!self.is_open() && !self.fading_out) {
+            return 0.0;
+        }
+
+        egui::emath::easing::cubic_out(self.fade)
+    }
+}
+
+/// UI methods
+impl ModalSpinner {
+    fn update_ui(&mut self, ctx: &egui::Context, content: impl FnOnce(&mut egui::Ui, &SpinnerCtx)) {
+        self.show_ui(ctx, content);
+        ModalSpinnerPlugin::register(ctx, self.effective_id(), self);
+    }
+
+    /// Renders the modal without registering the spinner with the plugin.
+    fn show_ui(&mut self, ctx: &egui::Context, content: impl FnOnce(&mut egui::Ui, &SpinnerCtx)) {
+        if ctx.viewport_id() != egui::ViewportId::ROOT
+            && self.secondary_viewports != ViewportCoverage::Full
+        {
+            self.show_secondary_viewport(ctx);
+            return;
+        }
+
+        self.apply_handle_requests(ctx);
+        self.apply_timeout();
+        self.apply_pending_close(ctx);
+
+        self.rendering_skipped = false;
+        self.content_panicked = false;
+
+        if !self.is_open() && !self.fading_out {
+            self.restore_focus(ctx);
+            self.notify_closed();
+            return;
+        }
+
+        if self.is_non_modal() {
+            self.update_tracking(ctx);
+            self.update_completion(ctx);
+            self.restore_focus(ctx);
+            self.show_background_indicator(ctx);
+            return;
+        }
+
+        #[cfg(feature = "frame-capture")]
+        let capture_requested = self.update_frame_capture(ctx);
+
+        if self.state == SpinnerState::Open {
+            self.update_open(ctx);
+        }
+
+        let id = self.effective_id();
+        let screen_rect = ctx.input(|i| i.screen_rect);
+
+        let opacity = self.update_fade(ctx);
+
+        self.report_visibility(screen_rect, opacity);
+
+        if opacity <= 0.0 && self.fading_out {
+            self.fading_out = false;
+            self.reset_fade();
+            self.restore_focus(ctx);
+            self.notify_closed();
+            return;
+        }
+
+        // The modal can not be laid out without a screen, e.g. while the window is minimized.
+        // The timers and the fade animation continue, so the modal is painted in its current
+        // state once the screen rect is restored.
+        if !is_positive_finite(screen_rect.width()) || !is_positive_finite(screen_rect.height()) {
+            self.rendering_skipped = true;
+            return;
+        }
+
+        #[cfg(feature = "debug-tools")]
+        if self.show_frame_stats {
+            let (time, dt) = ctx.input(|i| (i.time, i.unstable_dt));
+            self.frame_times.add(time, dt);
+        }
+
+        let spinner_ctx = self.spinner_ctx(opacity);
+
+        // Only a modal that blocks the input counts as active for guarded clicks.
+        self.record_active(ctx);
+
+        // The area only registers the layer of the modal and blocks the input to the layers
+        // beneath it. A new area is invisible during its first frame, so the modal itself is
+        // painted with a separate UI on the same layer. This way, the modal is already visible
+        // on the frame it is opened.
+        let re = egui::Area::new(id)
+            .order(self.order)
+            .movable(false)
+            .interactable(true)
+            .fixed_pos(screen_rect.left_top())
+            .fade_in(false)
+            .show(ctx, |ui| {
+                ui.allocate_response(screen_rect.size(), egui::Sense::click());
+            });
+
+        let layer_id = re.response.layer_id;
+
+        if !self.revealed {
+            ctx.move_to_top(layer_id);
+            Self::suppress_keyboard_focus(ctx, layer_id);
+            return;
+        }
+
+        let mut ui = egui::Ui::new(
+            ctx.clone(),
+            id.with("modal"),
+            egui::UiBuilder::new()
+                .layer_id(layer_id)
+                .max_rect(screen_rect)
+                .layout(egui::Layout::top_down(egui::Align::Center)),
+        );
+        ui.set_clip_rect(screen_rect);
+        ui.multiply_opacity(opacity);
+        ui.spacing_mut().item_spacing.y = self.style.item_spacing(ui.spacing());
+
+        #[cfg(feature = "frame-capture")]
+        self.ui_update_frozen_background(&mut ui, &screen_rect, capture_requested);
+
+        let fill_color = self.style.fill_color(ui.visuals());
+
+        ui.painter()
+            .rect_filled(screen_rect, self.style.rounding, fill_color);
+
+        if self.state == SpinnerState::Error {
+            self.ui_update_error(&mut ui, &screen_rect);
+        } else {
+            self.ui_update_spinner(&mut ui, &screen_rect);
+        }
+
+        self.ui_update_content(&mut ui, content, &spinner_ctx);
+
+        #[cfg(feature = "debug-tools")]
+        if self.show_frame_stats {
+            self.ui_update_frame_stats(&ui, &screen_rect);
+        }
+
+        ctx.move_to_top(layer_id);
+        Self::suppress_keyboard_focus(ctx, layer_id);
+    }
+
+    /// Updates the state of the open spinner and enters the modal on the first update after
+    /// it was opened.
+    fn update_open(&mut self, ctx: &egui::Context) {
+        self.update_tracking(ctx);
+
+        if self.pending_open {
+            // When opened again while fading out, the focus from the first opening is kept.
+            if self.focus_before_open.is_none() {
+                self.focus_before_open = ctx.memory(egui::Memory::focused);
+            }
+
+            Self::enter_modal(ctx);
+            self.opened_at = ctx.input(|i| i.time);
+            self.pending_open = false;
+        }
+
+        self.update_reveal(ctx);
+        self.update_completion(ctx);
+        self.update_escape_key(ctx);
+    }
+
+    /// Requests to cancel the task when the escape key is pressed while the cancel button is
+    /// displayed, and repaints once the escape hint should be displayed.
+    fn update_escape_key(&mut self, ctx: &egui::Context) {
+        if !self.show_cancel_button
+            || self.cancel_requested
+            || self.state != SpinnerState::Open
+            || self.is_non_modal()
+        {
+            return;
+        }
+
+        if ctx.input_mut(|i| i.consume_key(egui::Modifiers::NONE, egui::Key::Escape)) {
+            self.request_cancel();
+            return;
+        }
+
+        if let Some(delay) = self.escape_hint {
+            let remaining = delay.saturating_sub(self.elapsed());
+
+            if !remaining.is_zero() {
+                ctx.request_repaint_after(remaining);
+            }
+        }
+    }
+
+    /// Requests the task to be cancelled, like when the cancel button is clicked.
+    fn request_cancel(&mut self) {
+        self.cancel_requested = true;
+        self.handle.request_cancel();
+    }
+
+    /// Checks if the hint to cancel using the escape key should be displayed.
+    fn is_escape_hint_visible(&self) -> bool {
+        !self.cancel_requested
+            && self
+                .escape_hint
+                .is_some_and(|delay| self.elapsed() >= delay)
+    }
+
+    /// Sets the state of the item at the given index, if it exists.
+    fn set_item_state(&mut self, index: usize, state: ItemState) {
+        if let Some(item) = self.items.get_mut(index) {
+            *item = state;
+        }
+    }
+
+    /// Marks the item at the given index as failed and records the failure.
+    fn record_failure(&mut self, index: usize, reason: Option<String>) {
+        self.set_item_state(index, ItemState::Failed);
+        self.failures.push(ItemFailure { index, reason });
+    }
+
+    /// Enters the error state displaying the summary of the failed items, if enabled.
+    /// Returns true if the summary is displayed.
+    fn show_failure_summary(&mut self) -> bool {
+        if !self.failure_summary || self.failures.is_empty() || self.state != SpinnerState::Open {
+            return false;
+        }
+
+        let label = self
+            .failure_label
+            .replacen("{}", &self.failures.len().to_string(), 1);
+        self.fail(label);
+
+        true
+    }
+
+    /// Gets the rows of the list beneath the spinner, either the active tasks of the graph
+    /// or the steps of the task.
+    fn step_rows(&self) -> Vec<StepRow<'_>> {
+        self.task_graph.as_ref().map_or_else(
+            || steps::step_rows(&self.steps),
+            |graph| {
+                graph
+                    .active_labels()
+                    .map(|label| (label, StepState::Active))
+                    .collect()
+            },
+        )
+    }
+
+    /// Tracks the updates, the milestones and the progress rate while the spinner is open.
+    fn update_tracking(&mut self, ctx: &egui::Context) {
+        if let Some(progress) = self.task_graph.as_ref().map(TaskGraph::progress) {
+            self.set_progress(progress);
+        }
+
+        self.track_update();
+        self.update_milestones();
+        self.update_progress_rate(ctx);
+    }
+
+    /// Displays the spinner in a corner of the screen, without blocking the input, while the
+    /// spinner is not modal or the task runs in the background.
+    fn show_background_indicator(&self, ctx: &egui::Context) {
+        let margin = ctx.style().spacing.window_margin;
+        let corner = self.indicator_corner;
+
+        let offset = egui::vec2(
+            match corner.x() {
+                egui::Align::Min => margin.left,
+                egui::Align::Center => 0.0,
+                egui::Align::Max => -margin.right,
+            },
+            match corner.y() {
+                egui::Align::Min => margin.top,
+                egui::Align::Center => 0.0,
+                egui::Align::Max => -margin.bottom,
+            },
+        );
+
+        egui::Area::new(self.effective_id().with("background"))
+            .order(self.order)
+            .anchor(corner, offset)
+            .interactable(false)
+            .show(ctx, |ui| {
+                egui::Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.horizontal(|ui| self.ui_update_background_indicator(ui));
+                });
+            });
+    }
+
+    /// Displays the modal inside a secondary viewport according to the configured coverage.
+    /// The state of the spinner is not changed, it is only updated from the root viewport.
+    fn show_secondary_viewport(&self, ctx: &egui::Context) {
+        if self.secondary_viewports != ViewportCoverage::DimOnly || !self.revealed {
+            return;
+        }
+
+        if !self.is_open() && !self.fading_out {
+            return;
+        }
+
+        let screen_rect = ctx.input(|i| i.screen_rect);
+
+        if !is_positive_finite(screen_rect.width()) || !is_positive_finite(screen_rect.height()) {
+            return;
+        }
+
+        let re = egui::Area::new(self.effective_id().with("viewport"))
+            .order(self.order)
+            .movable(false)
+            .interactable(true)
+            .fixed_pos(screen_rect.left_top())
+            .fade_in(false)
+            .show(ctx, |ui| {
+                ui.allocate_response(screen_rect.size(), egui::Sense::click());
+            });
+
+        let layer_id = re.response.layer_id;
+        let opacity = egui::emath::easing::cubic_out(self.fade);
+        let fill_color = self.style.fill_color(&ctx.style().visuals);
+
+        ctx.layer_painter(layer_id).rect_filled(
+            screen_rect,
+            self.style.rounding,
+            fill_color.gamma_multiply(opacity),
+        );
+
+        ctx.move_to_top(layer_id);
+
+        if self.fade > 0.0 && self.fade < 1.0 {
+            ctx.request_repaint();
+        }
+    }
+
+    /// Advances the fade animation towards the current state and gets the opacity of the modal.
+    fn update_fade(&mut self, ctx: &egui::Context) -> f32 {
+        if !self.revealed {
+            return 0.0;
+        }
+
+        let open = self.is_open();
+        let target = if open { 1.0 } else { 0.0 };
+        let animated = if open { self.fade_in } else { self.fade_out };
+
+        let (time, predicted_dt) = ctx.input(|i| (i.time, i.predicted_dt));
+        let animation_time = ctx.style().animation_time;
+
+        // Half of the predicted frame time is used for the first step, so that the modal
+        // is already partially visible on the frame it is opened.
+        #[allow(clippy::cast_possible_truncation)]
+        let dt = self
+            .fade_time
+            .map_or(predicted_dt / 2.0, |fade_time| (time - fade_time) as f32);
+        self.fade_time = Some(time);
+
+        if !animated || animation_time <= 0.0 {
+            self.fade = target;
+        } else {
+            let step = dt.max(0.0) / animation_time;
+            self.fade = if open {
+                (self.fade + step).min(1.0)
+            } else {
+                (self.fade - step).max(0.0)
+            };
+        }
+
+        if (open && self.fade < 1.0) || (!open && self.fade > 0.0) {
+            ctx.request_repaint();
+        }
+
+        egui::emath::easing::cubic_out(self.fade)
+    }
+
+    /// Advances the completion animation and closes the spinner once it finished.
+    fn update_completion(&mut self, ctx: &egui::Context) {
+        if self.completion.is_none() {
+            return;
+        }
+
+        let time = ctx.input(|i| i.time);
+        let started = *self.completion_started.get_or_insert(time);
+
+        if time - started >= self.completion_duration.as_secs_f64() {
+            self.close();
+        } else {
+            ctx.request_repaint();
+        }
+    }
+
+    /// Calls the close callback, if the modal disappeared since the open callback was called.
+    fn notify_closed(&mut self) {
+        if !self.lifecycle_open {
+            return;
+        }
+
+        self.lifecycle_open = false;
+
+        if let Some(callback) = &self.on_close {
+            callback.call(&());
+        }
+    }
+
+    /// Reports the area covered by the modal to the visibility callback, if set.
+    fn report_visibility(&self, rect: egui::Rect, opacity: f32) {
+        if let Some(callback) = &self.on_visibility {
+            callback.call(&OverlayVisibility { rect, opacity });
+        }
+    }
+
+    /// Resets the fade animation, so that the modal fades in from zero the next time it opens.
+    const fn reset_fade(&mut self) {
+        self.fade = 0.0;
+        self.fade_time = None;
+    }
+
+    /// Updates the captured frame and requests a screenshot when the spinner opens.
+    /// Returns true if a screenshot was requested this frame.
+    #[cfg(feature = "frame-capture")]
+    fn update_frame_capture(&mut self, ctx: &egui::Context) -> bool {
+        let request = self.freeze_background && self.pending_open;
+
+        if request {
+            self.frame_capture.request(ctx);
+        }
+
+        self.frame_capture.update(ctx, self.desaturate_background);
+
+        request
+    }
+
+    #[cfg(feature = "frame-capture")]
+    fn ui_update_frozen_background(
+        &self,
+        ui: &mut egui::Ui,
+        screen_rect: &egui::Rect,
+        capture_requested: bool,
+    ) {
+        // Hide the modal while the screenshot is taken,
+        // so the screenshot contains only the UI beneath it.
+        if capture_requested {
+            ui.multiply_opacity(0.0);
+        }
+
+        if let Some(texture) = self.frame_capture.texture() {
+            let uv = egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1.0, 1.0));
+            ui.painter()
+                .image(texture.id(), *screen_rect, uv, egui::Color32::WHITE);
+        }
+    }
+
+    /// Creates the state passed to the content closure,
+    /// with the given opacity of the modal animation.
+    fn spinner_ctx(&self, opacity: f32) -> SpinnerCtx {
+        let phase = if !self.is_open() {
+            SpinnerPhase::FadingOut
+        } else if opacity < 1.0 {
+            SpinnerPhase::FadingIn
+        } else {
+            SpinnerPhase::Open
+        };
+
+        SpinnerCtx {
+            elapsed: self.elapsed(),
+            progress: self.progress,
+            phase,
+            cancel_requested: self.cancel_requested,
+        }
+    }
+
+    /// Stores the current pass as the last pass in which the spinner was active.
+    fn record_active(&self, ctx: &egui::Context) {
+        let pass_nr = ctx.cumulative_pass_nr();
+        let id = self.effective_id().with(LAST_ACTIVE_ID);
+
+        ctx.data_mut(|d| {
+            d.insert_temp(id, pass_nr);
+            d.insert_temp(egui::Id::new(LAST_ACTIVE_ID), pass_nr);
+        });
+    }
+
+    /// Checks if a spinner was active during the current or one of the last two passes,
+    /// according to the pass number stored under the given ID.
+    fn was_recently_active(ctx: &egui::Context, id: egui::Id) -> bool {
+        let pass_nr = ctx.cumulative_pass_nr();
+
+        ctx.data(|d| d.get_temp::<u64>(id))
+            .is_some_and(|last_active| last_active + 2 >= pass_nr)
+    }
+
+    /// Marks the modal as revealed once the spinner has been open for the show delay.
+    fn update_reveal(&mut self, ctx: &egui::Context) {
+        if self.revealed {
+            return;
+        }
+
+        let open_for = ctx.input(|i| i.time) - self.opened_at;
+        let remaining = self.show_after.as_secs_f64() - open_for;
+
+        if remaining <= 0.0 {
+            self.revealed = true;
+        } else if let Ok(remaining) = Duration::try_from_secs_f64(remaining) {
+            ctx.request_repaint_after(remaining);
+        }
+    }
+
+    /// Closes the spinner if it is open for longer than the timeout.
+    fn apply_timeout(&mut self) {
+        let Some(timeout) = self.timeout else {
+            return;
+        };
+
+        // A failed task waits for the user to retry or dismiss it.
+        if self.state != SpinnerState::Open || self.close_pending || self.elapsed() < timeout {
+            return;
+        }
+
+        log::warn!(
+            "The modal spinner was closed because it was open for longer than the timeout of {timeout:?}."
+        );
+
+        self.timed_out = true;
+        self.close();
+    }
+
+    /// Closes the spinner if it was closed before the minimum display time elapsed
+    /// and the time has now elapsed.
+    fn apply_pending_close(&mut self, ctx: &egui::Context) {
+        if !self.close_pending {
+            return;
+        }
+
+        let remaining = self.min_display_time.saturating_sub(self.elapsed());
+
+        if remaining.is_zero() {
+            self.close();
+        } else {
+            ctx.request_repaint_after(remaining);
+        }
+    }
+
+    /// Applies the requests made through the handles of the spinner since the last update.
+    /// Requests made while the spinner is closed are discarded.
+    fn apply_handle_requests(&mut self, ctx: &egui::Context) {
+        self.handle.set_context(ctx);
+
+        let close_requested = self.handle.take_close_request() | self.handle.take_guard_close();
+        let progress = self.handle.take_progress();
+        let message = self.handle.take_message();
+        let cancel_acknowledged = self.handle.take_cancel_acknowledgement();
+        let items = self.handle.take_items();
+        let updates = u64::from(progress.is_some())
+            + u64::from(message.is_some())
+            + u64::try_from(items.len()).unwrap_or(u64::MAX);
+
+        if !self.is_open() {
+            self.handle.record_dropped(updates);
+            return;
+        }
+
+        self.handle.record_applied(updates);
+
+        if let Some(message) = message {
+            self.message = message;
+        }
+
+        if let Some(progress) = progress {
+            self.set_progress(progress);
+        }
+
+        for item in items {
+            if item.state == ItemState::Failed {
+                self.record_failure(item.index, item.reason);
+            } else {
+                self.set_item_state(item.index, item.state);
+            }
+        }
+
+        if let Some(progress) = self.progress_source.as_ref().and_then(SpinnerProgress::get) {
+            self.set_progress(progress);
+        }
+
+        if cancel_acknowledged && self.cancel_requested {
+            self.cancel_acknowledged = true;
+            self.close();
+        }
+
+        if close_requested {
+            self.close();
+        }
+    }
+
+    /// Prepares the context when the modal opens, so that UI elements
+    /// beneath the modal don't stay active while it is open.
+    fn enter_modal(ctx: &egui::Context) {
+        // Close popups and context menus that were opened before the modal,
+        // otherwise they would still be visible after the modal closes.
+        ctx.memory_mut(egui::Memory::close_popup);
+        ctx.data_mut(|d| {
+            d.remove::<egui::menu::BarState>(egui::Id::new(CONTEXT_MENU_ID));
+        });
+
+        // Cancel drags that are in progress, e.g. tabs of a dock area, so they
+        // don't keep tracking the pointer beneath the modal.
+        ctx.stop_dragging();
+        egui::DragAndDrop::clear_payload(ctx);
+
+        // Release the pointer if a button is held down while the modal opens.
+        // Otherwise, releasing the button after the modal closed would still click the
+        // widget beneath the modal or resume a drag, like moving a slider.
+        if ctx.input(|i| i.pointer.any_down()) {
+            let options = ctx.options(Clone::clone);
+
+            ctx.input_mut(|i| {
+                i.events
+                    .retain(|event| !matches!(event, egui::Event::PointerButton { .. }));
+                i.pointer = Self::released_pointer(&i.pointer, i.time, &options);
+            });
+        }
+    }
+
+    /// Creates a pointer state without any pressed button, at the position of the given
+    /// pointer. egui has no API to release the buttons of a pointer state, so a new state is
+    /// created from the position instead.
+    fn released_pointer(
+        pointer: &egui::PointerState,
+        time: f64,
+        options: &egui::Options,
+    ) -> egui::PointerState {
+        let input = egui::RawInput {
+            time: Some(time),
+            events: pointer
+                .latest_pos()
+                .map(egui::Event::PointerMoved)
+                .into_iter()
+                .collect(),
+            ..Default::default()
+        };
+
+        egui::InputState::default()
+            .begin_pass(input, false, 1.0, options)
+            .pointer
+    }
+
+    /// Updates the smoothed progress rate when the progress changed since the last sample.
+    fn update_progress_rate(&mut self, ctx: &egui::Context) {
+        /// The weight of a new measurement in the smoothed rate.
+        const SMOOTHING: f32 = 0.3;
+
+        let Some(progress) = self.progress else {
+            self.progress_sample = None;
+            self.progress_rate = None;
+            return;
+        };
+
+        let time = ctx.input(|i| i.time);
+
+        let Some((sample_time, sample_progress)) = self.progress_sample else {
+            self.progress_sample = Some((time, progress));
+            return;
+        };
+
+        // A decreasing progress means that a new task was started.
+        if progress < sample_progress {
+            self.progress_sample = Some((time, progress));
+            self.progress_rate = None;
+            return;
+        }
+
+        #[allow(clippy::cast_possible_truncation)]
+        let dt = (time - sample_time) as f32;
+
+        if progress <= sample_progress || dt <= 0.0 {
+            return;
+        }
+
+        let rate = (progress - sample_progress) / dt;
+
+        self.progress_rate = Some(
+            self.progress_rate
+                .map_or(rate, |smoothed| egui::lerp(smoothed..=rate, SMOOTHING)),
+        );
+        self.progress_sample = Some((time, progress));
+    }
+
+    /// Records the milestones that were reached since the last update.
+    fn update_milestones(&mut self) {
+        let elapsed = self.elapsed();
+
+        while let Some(milestone) = self.milestones.get(self.next_milestone) {
+            if elapsed < *milestone {
+                break;
+            }
+
+            self.reached_milestones.push(*milestone);
+            self.next_milestone += 1;
+        }
+    }
+
+    fn track_update(&mut self) {
+        if self.is_stale() {
+            log::warn!(
+                "The modal spinner was open but not updated for {:?}. \
+                Make sure the update method of the spinner is called every frame.",
+                self.time_since_update()
+            );
+        }
+
+        self.last_update = Some(self.now());
+    }
+
+    /// Gets the time since the spinner was last updated,
+    /// or since it was opened if it was not updated yet.
+    fn time_since_update(&self) -> Duration {
+        self.now()
+            .saturating_duration_since(self.last_update.unwrap_or(self.timestamp))
+    }
+
+    /// Gets the current time of the clock the spinner measures the elapsed time with.
+    #[cfg(not(feature = "testing"))]
+    #[allow(clippy::unused_self)] // The clock of the spinner is only simulated when testing.
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    /// Gets the current time of the clock the spinner measures the elapsed time with,
+    /// including the time the simulated clock was advanced by.
+    #[cfg(feature = "testing")]
+    fn now(&self) -> Instant {
+        let now = Instant::now();
+        now.checked_add(self.test_clock.offset).unwrap_or(now)
+    }
+
+    /// Gives the keyboard focus back to the widget that was focused when the modal opened.
+    ///
+    /// The focus is moved explicitly instead of leaving it to egui, so that the focus ring
+    /// of the widget reappears where it was rather than the focus jumping to another widget.
+    /// If the widget no longer exists, egui clears the focus again.
+    fn restore_focus(&mut self, ctx: &egui::Context) {
+        if let Some(id) = self.focus_before_open.take() {
+            ctx.memory_mut(|m| m.request_focus(id));
+        }
+    }
+
+    /// The modal area only blocks pointer input. To also suppress keyboard input,
+    /// we make sure no widget outside the modal keeps the keyboard focus.
+    pub(crate) fn suppress_keyboard_focus(ctx: &egui::Context, modal_layer: egui::LayerId) {
+        let Some(focused) = ctx.memory(egui::Memory::focused) else {
+            return;
+        };
+
+        let inside_modal = ctx
+            .read_response(focused)
+            .is_some_and(|re| re.layer_id == modal_layer);
+
+        if !inside_modal {
+            ctx.memory_mut(|m| m.surrender_focus(focused));
+        }
+    }
+
+    fn ui_update_spinner(&mut self, ui: &mut egui::Ui, screen_rect: &egui::Rect) {
+        let spinner_h = self.style.spinner_size(ui.spacing());
+
+        let mut margin = screen_rect.height() / 2.0 - spinner_h / 2.0;
+
+        // The elapsed time is only displayed if the font of the text style is available,
+        // as egui would otherwise panic when laying out the label.
+        let text_height = if self.show_elapsed_time {
+            row_height(ui, &self.style.text_style)
+        } else {
+            None
+        };
+
+        if let Some(height) = text_height {
+            margin -= ui.spacing().item_spacing.y.mul_add(2.0, height / 2.0);
+        }
+
+        // Like the elapsed time, the message is only displayed if the font is available.
+        let message_height = if self.message.is_some() {
+            row_height(ui, &self.style.text_style)
+        } else {
+            None
+        };
+
+        if let Some(height) = message_height {
+            margin -= ui.spacing().item_spacing.y.mul_add(2.0, height / 2.0);
+        }
+
+        let step_rows = self.step_rows();
+        let steps_height = steps::steps_height(ui, &self.style, step_rows.len());
+
+        if let Some(height) = steps_height {
+            margin -= ui.spacing().item_spacing.y.mul_add(2.0, height / 2.0);
+        }
+
+        if self.progress.is_some() {
+            let height = progress::progress_bar_height(ui);
+            margin -= ui.spacing().item_spacing.y.mul_add(2.0, height / 2.0);
+        }
+
+        let batch_map_height = batch::batch_map_height(&self.style, self.items.len());
+
+        if let Some(height) = batch_map_height {
+            margin -= ui.spacing().item_spacing.y.mul_add(2.0, height / 2.0);
+        }
+
+        // Like the elapsed time, the remaining time is only displayed if the font is available.
+        let eta = if self.show_eta { self.eta() } else { None };
+        let eta_height = eta.and_then(|_| row_height(ui, &self.style.text_style));
+
+        if let Some(height) = eta_height {
+            margin -= ui.spacing().item_spacing.y.mul_add(2.0, height / 2.0);
+        }
+
+        // Like the elapsed time, the cancel button is only displayed if the font is available.
+        let show_cancel_button =
+            self.show_cancel_button && row_height(ui, &self.style.text_style).is_some();
+
+        if show_cancel_button {
+            let mut height = ui.spacing().interact_size.y;
+
+            if self.is_escape_hint_visible() {
+                height += row_height(ui, &self.style.text_style).unwrap_or_default()
+                    + ui.spacing().item_spacing.y;
+            }
+
+            margin -= ui.spacing().item_spacing.y.mul_add(2.0, height / 2.0);
+        }
+
+        // Like the elapsed time, the background button is only displayed if the font is
+        // available.
+        let show_background_button =
+            self.show_background_button && row_height(ui, &self.style.text_style).is_some();
+
+        if show_background_button {
+            let height = ui.spacing().interact_size.y;
+            margin -= ui.spacing().item_spacing.y.mul_add(2.0, height / 2.0);
+        }
+
+        ui.add_space(margin);
+
+        self.ui_update_spinner_widget(ui, spinner_h);
+
+        if message_height.is_some() {
+            self.ui_update_message(ui);
+        }
+
+        if steps_height.is_some() {
+            ui.add_space(ui.spacing().item_spacing.y);
+            steps::ui_steps(ui, &self.style, &step_rows);
+        }
+
+        if let Some(progress) = self.progress {
+            ui.add_space(ui.spacing().item_spacing.y);
+            progress::ui_progress_bar(ui, &self.style, progress, self.show_progress_percentage);
+        }
+
+        if batch_map_height.is_some() {
+            ui.add_space(ui.spacing().item_spacing.y);
+            batch::ui_batch_map(ui, &self.style, &self.items);
+        }
+
+        if let (Some(eta), Some(_)) = (eta, eta_height) {
+            self.ui_update_eta(ui, eta);
+        }
+
+        if text_height.is_some() {
+            self.ui_update_elapsed_time(ui);
+        }
+
+        if show_cancel_button {
+            self.ui_update_cancel_button(ui);
+        }
+
+        if show_background_button {
+            self.ui_update_background_button(ui);
+        }
+    }
+
+    /// Adds the content of the application, catching its panics if enabled.
+    fn ui_update_content(
+        &mut self,
+        ui: &mut egui::Ui,
+        content: impl FnOnce(&mut egui::Ui, &SpinnerCtx),
+        spinner_ctx: &SpinnerCtx,
+    ) {
+        if !self.catch_content_panics {
+            content(ui, spinner_ctx);
+            return;
+        }
+
+        let result = panic::catch_unwind(panic::AssertUnwindSafe(|| content(ui, spinner_ctx)));
+
+        let Err(payload) = result else {
+            return;
+        };
+
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|message| (*message).to_owned())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "Unknown panic".to_owned());
+
+        log::error!("The content of the modal spinner panicked: {message}");
+
+        self.content_panicked = true;
+        self.content_panic = Some(message);
+
+        let style = OverlayStyle {
+            text_color: Some(ui.visuals().error_fg_color),
+            ..self.style.clone()
+        };
+
+        ui.add_space(ui.spacing().item_spacing.y);
+        style.ui_label(ui, &self.content_panic_label);
+    }
+
+    fn ui_update_background_button(&mut self, ui: &mut egui::Ui) {
+        ui.add_space(ui.spacing().item_spacing.y);
+
+        let text = self.button_text(&self.background_label);
+
+        if ui.button(text).clicked() {
+            self.in_background = true;
+        }
+    }
+
+    fn ui_update_background_indicator(&self, ui: &mut egui::Ui) {
+        let size = ui.spacing().interact_size.y;
+
+        if let Some(widget) = &self.custom_spinner {
+            widget.ui(ui, size, self.elapsed(), self.progress);
+        } else {
+            // The modal background is not painted beneath the indicator, so the spinner uses
+            // the colors of the current egui style unless a color is set.
+            let color = self
+                .style
+                .spinner_color
+                .unwrap_or_else(|| ui.visuals().strong_text_color());
+
+            indicator::ui_spinner_with_color(ui, self.style.spinner_style, size, color);
+        }
+
+        if let Some(progress) = self.progress {
+            progress::ui_progress_bar(ui, &self.style, progress, self.show_progress_percentage);
+        }
+
+        if self.show_elapsed_time {
+            self.style.ui_label(ui, self.elapsed_label_text());
+        }
+    }
+
+    fn ui_update_error(&mut self, ui: &mut egui::Ui, screen_rect: &egui::Rect) {
+        let icon_h = self.style.spinner_size(ui.spacing());
+
+        let mut margin = screen_rect.height() / 2.0 - icon_h / 2.0;
+
+        // Like the elapsed time, the message and the buttons are only displayed
+        // if the font is available.
+        let text_height = row_height(ui, &self.style.text_style);
+
+        if let Some(height) = text_height {
+            margin -= ui.spacing().item_spacing.y.mul_add(2.0, height / 2.0);
+
+            let height = ui.spacing().interact_size.y;
+            margin -= ui.spacing().item_spacing.y.mul_add(2.0, height / 2.0);
+
+            // The list of failures is expanded below the header without moving the content.
+            if !self.failures.is_empty() {
+                margin -= ui.spacing().item_spacing.y.mul_add(2.0, height / 2.0);
+            }
+        }
+
+        ui.add_space(margin);
+
+        indicator::ui_completion(ui, &self.style, icon_h, Completion::Failure, 1.0);
+
+        if text_height.is_none() {
+            return;
+        }
+
+        if let Some(error) = &self.error {
+            ui.add_space(ui.spacing().item_spacing.y);
+            self.style.ui_label(ui, error);
+        }
+
+        if !self.failures.is_empty() {
+            ui.add_space(ui.spacing().item_spacing.y);
+            self.ui_update_failures(ui);
+        }
+
+        ui.add_space(ui.spacing().item_spacing.y);
+        self.ui_update_error_buttons(ui);
+    }
+
+    fn ui_update_failures(&self, ui: &mut egui::Ui) {
+        /// The maximum height of the expanded list, which is scrollable if it is higher.
+        const MAX_LIST_HEIGHT: f32 = 120.0;
+
+        let width = self.style.progress_bar_width().min(ui.available_width());
+
+        ui.allocate_ui_with_layout(
+            egui::vec2(width, ui.spacing().interact_size.y),
+            egui::Layout::top_down(egui::Align::Min),
+            |ui| {
+                egui::CollapsingHeader::new(self.button_text(&self.failure_details_label))
+                    .id_salt("failures")
+                    .show(ui, |ui| {
+                        egui::ScrollArea::vertical()
+                            .max_height(MAX_LIST_HEIGHT)
+                            .show(ui, |ui| {
+                                for failure in &self.failures {
+                                    self.style.ui_label(ui, failure.label());
+                                }
+                            });
+                    });
+            },
+        );
+    }
+
+    fn ui_update_error_buttons(&mut self, ui: &mut egui::Ui) {
+        let retry = self.button_text(&self.retry_label);
+        let dismiss = self.button_text(&self.dismiss_label);
+
+        // The buttons are placed in a row, which is centered by allocating its exact width.
+        let button_width = |text: &egui::RichText| {
+            let galley = egui::WidgetText::from(text.clone()).into_galley(
+                ui,
+                Some(egui::TextWrapMode::Extend),
+                f32::INFINITY,
+                egui::TextStyle::Button,
+            );
+
+            ui.spacing()
+                .button_padding
+                .x
+                .mul_add(2.0, galley.size().x)
+                .max(ui.spacing().interact_size.x)
+        };
+
+        let width = button_width(&retry) + ui.spacing().item_spacing.x + button_width(&dismiss);
+        let size = egui::vec2(
+            width.min(ui.available_width()),
+            ui.spacing().interact_size.y,
+        );
+
+        let (retry_clicked, dismiss_clicked) = ui
+            .allocate_ui_with_layout(
+                size,
+                egui::Layout::left_to_right(egui::Align::Center),
+                |ui| (ui.button(retry).clicked(), ui.button(dismiss).clicked()),
+            )
+            .inner;
+
+        if retry_clicked {
+            self.error_action = Some(ErrorAction::Retry);
+        }
+
+        if dismiss_clicked {
+            self.error_action = Some(ErrorAction::Dismiss);
+            self.close();
+        }
+    }
+
+    fn button_text(&self, label: &str) -> egui::RichText {
+        let mut text = egui::RichText::new(label).text_style(self.style.text_style.clone());
+
+        if let Some(color) = self.style.text_color {
+            text = text.color(color);
+        }
+
+        text
+    }
+
+    fn ui_update_cancel_button(&mut self, ui: &mut egui::Ui) {
+        ui.add_space(ui.spacing().item_spacing.y);
+
+        let label = if self.cancel_requested {
+            &self.cancelling_label
+        } else {
+            &self.cancel_label
+        };
+
+        let text = self.button_text(label);
+
+        // The button stays disabled once clicked, to show that the request was received.
+        let re = ui.add_enabled(!self.cancel_requested, egui::Button::new(text));
+
+        if re.clicked() {
+            self.request_cancel();
+        }
+
+        if self.is_escape_hint_visible() {
+            let style = OverlayStyle {
+                text_color: Some(ui.visuals().weak_text_color()),
+                ..self.style.clone()
+            };
+
+            ui.add_space(ui.spacing().item_spacing.y);
+            style.ui_label(ui, &self.escape_hint_label);
+        }
+    }
+
+    fn ui_update_message(&self, ui: &mut egui::Ui) {
+        if let Some(message) = &self.message {
+            ui.add_space(ui.spacing().item_spacing.y);
+            self.style.ui_label(ui, message);
+        }
+    }
+
+    fn ui_update_eta(&self, ui: &mut egui::Ui, eta: Duration) {
+        let seconds = eta.as_secs_f32().ceil().to_string();
+
+        ui.add_space(ui.spacing().item_spacing.y);
+        self.style
+            .ui_label(ui, self.eta_label.replacen("{}", &seconds, 1));
+    }
+
+    fn ui_update_elapsed_time(&self, ui: &mut egui::Ui) {
+        ui.add_space(ui.spacing().item_spacing.y);
+        self.style.ui_label(ui, self.elapsed_label_text());
+    }
+
+    fn ui_update_spinner_widget(&self, ui: &mut egui::Ui, size: f32) {
+        let Some(completion) = self.completion else {
+            if let Some(widget) = &self.custom_spinner {
+                widget.ui(ui, size, self.elapsed(), self.progress);
+            } else {
+                indicator::ui_spinner(ui, &self.style, size);
+            }
+
+            return;
+        };
+
+        // The result is drawn during the first half of the completion duration
+        // and then stays visible until the modal closes.
+        let elapsed = self
+            .completion_started
+            .map_or(0.0, |started| ui.input(|i| i.time) - started);
+        let draw_time = self.completion_duration.as_secs_f64() / 2.0;
+
+        #[allow(clippy::cast_possible_truncation)]
+        let progress = (elapsed / draw_time) as f32;
+
+        indicator::ui_completion(ui, &self.style, size, completion, progress);
+    }
+
+    #[cfg(feature = "debug-tools")]
+    fn ui_update_frame_stats(&self, ui: &egui::Ui, screen_rect: &egui::Rect) {
+        let Some(font_id) = available_font_id(ui, &egui::TextStyle::Monospace) else {
+            return;
+        };
+
+        let frame_time = self.frame_times.average().unwrap_or_default();
+        let fps = if frame_time > 0.0 {
+            1.0 / frame_time
+        } else {
+            0.0
+        };
+
+        let margin = ui.spacing().window_margin;
+
+        ui.painter().text(
+            screen_rect.left_top() + egui::vec2(margin.left, margin.top),
+            egui::Align2::LEFT_TOP,
+            format!("FPS: {fps:.0}\nFrame time: {:.1} ms", frame_time * 1000.0),
+            font_id,
+            ui.visuals().strong_text_color(),
+        );
+    }
+
+    fn elapsed_label_text(&self) -> String {
+        if let Some(formatter) = &self.elapsed_formatter {
+            return formatter.call(&self.elapsed());
+        }
+
+        self.elapsed_label
+            .replacen("{}", &self.elapsed_format.format(self.elapsed()), 1)
+    }
+}
@@ -0,0 +1,264 @@
+//! Plugin rendering open spinners that were not updated during a frame.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Weak};
+
+use web_time::Instant;
+
+use crate::handle::SpinnerHandle;
+use crate::indicator::{self, Completion, CustomSpinner};
+use crate::overlay::ModalSpinner;
+use crate::progress;
+use crate::style::OverlayStyle;
+use crate::utils::is_positive_finite;
+
+/// The ID under which the context stores whether the plugin is installed.
+const INSTALLED_ID: &str = "egui_modal_spinner_plugin_installed";
+/// The ID under which the context stores the spinners registered with the plugin.
+const REGISTRY_ID: &str = "egui_modal_spinner_plugin_registry";
+
+/// Plugin that renders open spinners at the end of every frame in which they were not updated.
+///
+/// Normally, an open spinner is only visible while its update method is called every frame.
+/// If the update call site is inside a branch that stops executing, e.g. a tab that is
+/// no longer shown, the spinner disappears even though the application still considers it
+/// open. Once the plugin is installed, every spinner that is updated once registers itself.
+/// If a registered spinner is open but was not updated during a frame, the plugin renders
+/// it at the end of the frame instead, until the spinner is closed or dropped.
+///
+/// The plugin renders the modal with the spinner, or the error icon, and the progress bar
+/// as they were configured during the last update. Labels, buttons and the additional
+/// content of [`ModalSpinner::update_with_content`] are not rendered. Requests made through
+/// the handles of the spinner are left for the spinner itself and are applied once the
+/// application updates it again. A spinner closed by a handle is no longer rendered.
+///
+/// # Example
+/// ```rust
+/// use egui_modal_spinner::ModalSpinnerPlugin;
+///
+/// let ctx = egui::Context::default();
+/// ModalSpinnerPlugin::install(&ctx);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct ModalSpinnerPlugin;
+
+impl ModalSpinnerPlugin {
+    /// Installs the plugin in the given context.
+    ///
+    /// Installing the plugin more than once has no effect.
+    pub fn install(ctx: &egui::Context) {
+        if Self::is_installed(ctx) {
+            return;
+        }
+
+        ctx.data_mut(|d| d.insert_temp(egui::Id::new(INSTALLED_ID), true));
+        ctx.on_end_pass("egui_modal_spinner", Arc::new(Self::on_end_pass));
+    }
+
+    /// Checks if the plugin is installed in the given context.
+    pub fn is_installed(ctx: &egui::Context) -> bool {
+        ctx.data(|d| d.get_temp::<bool>(egui::Id::new(INSTALLED_ID)))
+            .unwrap_or(false)
+    }
+
+    /// Registers the given spinner after it was updated,
+    /// or unregisters it if it is no longer visible.
+    pub(crate) fn register(ctx: &egui::Context, id: egui::Id, spinner: &ModalSpinner) {
+        if !Self::is_installed(ctx) {
+            return;
+        }
+
+        let pass_nr = ctx.cumulative_pass_nr();
+
+        ctx.data_mut(|d| {
+            let registry = d.get_temp_mut_or_default::<Registry>(egui::Id::new(REGISTRY_ID));
+
+            if spinner.should_disable_ui() {
+                registry.spinners.insert(
+                    id,
+                    RegisteredSpinner {
+                        view: spinner.plugin_view(),
+                        link: spinner.plugin_link().downgrade(),
+                        pass_nr,
+                        rendered_by_plugin: false,
+                    },
+                );
+            } else {
+                registry.spinners.remove(&id);
+            }
+        });
+    }
+
+    fn on_end_pass(ctx: &egui::Context) {
+        let id = egui::Id::new(REGISTRY_ID);
+
+        // The registry is taken out of the context while rendering,
+        // so that the context can be used by the spinners.
+        let Some(mut registry) = ctx.data_mut(|d| d.remove_temp::<Registry>(id)) else {
+            return;
+        };
+
+        let pass_nr = ctx.cumulative_pass_nr();
+
+        registry
+            .spinners
+            .retain(|id, spinner| spinner.update_if_missed(ctx, *id, pass_nr));
+
+        ctx.data_mut(|d| d.insert_temp(id, registry));
+    }
+}
+
+/// Links a spinner to its registration, so that the plugin
+/// knows when the spinner is closed or dropped.
+#[derive(Debug)]
+pub(crate) struct PluginLink(Arc<AtomicBool>);
+
+impl PluginLink {
+    pub(crate) fn new() -> Self {
+        Self(Arc::new(AtomicBool::new(false)))
+    }
+
+    pub(crate) fn set_open(&self, open: bool) {
+        self.0.store(open, Ordering::Relaxed);
+    }
+
+    pub(crate) fn downgrade(&self) -> Weak<AtomicBool> {
+        Arc::downgrade(&self.0)
+    }
+}
+
+impl Clone for PluginLink {
+    /// A cloned spinner is a separate spinner,
+    /// so the clone must not share the link with the original.
+    fn clone(&self) -> Self {
+        Self(Arc::new(AtomicBool::new(self.0.load(Ordering::Relaxed))))
+    }
+}
+
+/// The spinners registered with the plugin.
+#[derive(Debug, Clone, Default)]
+struct Registry {
+    spinners: HashMap<egui::Id, RegisteredSpinner>,
+}
+
+/// The state of a spinner needed to render its modal, taken during its last update.
+#[derive(Debug, Clone)]
+pub(crate) struct PluginView {
+    pub order: egui::Order,
+    pub style: OverlayStyle,
+    /// If the spinner displays an error.
+    pub failed: bool,
+    pub progress: Option<f32>,
+    pub show_progress_percentage: bool,
+    pub custom_spinner: Option<CustomSpinner>,
+    /// The time the spinner was opened.
+    pub started: Instant,
+    /// The handle of the spinner, used to read the pending requests without taking them.
+    pub handle: SpinnerHandle,
+}
+
+impl PluginView {
+    /// Renders the modal blocking the input to the layers beneath it.
+    fn show(&self, ctx: &egui::Context, id: egui::Id) {
+        let screen_rect = ctx.input(|i| i.screen_rect);
+
+        if !is_positive_finite(screen_rect.width()) || !is_positive_finite(screen_rect.height()) {
+            return;
+        }
+
+        let re = egui::Area::new(id)
+            .order(self.order)
+            .movable(false)
+            .interactable(true)
+            .fixed_pos(screen_rect.left_top())
+            .fade_in(false)
+            .show(ctx, |ui| {
+                ui.allocate_response(screen_rect.size(), egui::Sense::click());
+            });
+
+        let layer_id = re.response.layer_id;
+
+        let mut ui = egui::Ui::new(
+            ctx.clone(),
+            id.with("modal"),
+            egui::UiBuilder::new()
+                .layer_id(layer_id)
+                .max_rect(screen_rect)
+                .layout(egui::Layout::top_down(egui::Align::Center)),
+        );
+        ui.set_clip_rect(screen_rect);
+
+        let fill_color = self.style.fill_color(ui.visuals());
+        ui.painter()
+            .rect_filled(screen_rect, self.style.rounding, fill_color);
+
+        let size = self.style.spinner_size(ui.spacing());
+        ui.add_space(screen_rect.height() / 2.0 - size / 2.0);
+
+        if self.failed {
+            indicator::ui_completion(&mut ui, &self.style, size, Completion::Failure, 1.0);
+        } else if let Some(widget) = &self.custom_spinner {
+            let elapsed = Instant::now().saturating_duration_since(self.started);
+            widget.ui(&mut ui, size, elapsed, self.progress);
+        } else {
+            indicator::ui_spinner(&mut ui, &self.style, size);
+        }
+
+        if let Some(progress) = self.handle.peek_progress().or(self.progress) {
+            ui.add_space(self.style.item_spacing(ui.spacing()));
+            progress::ui_progress_bar(
+                &mut ui,
+                &self.style,
+                progress,
+                self.show_progress_percentage,
+            );
+        }
+
+        ctx.move_to_top(layer_id);
+        ModalSpinner::suppress_keyboard_focus(ctx, layer_id);
+    }
+}
+
+/// A spinner as it was configured during its last update.
+#[derive(Debug, Clone)]
+struct RegisteredSpinner {
+    view: PluginView,
+    /// Link to the spinner owned by the application. Fails to upgrade if it was dropped.
+    link: Weak<AtomicBool>,
+    /// The pass in which the spinner was last updated by the application.
+    pass_nr: u64,
+    /// If the plugin already rendered the spinner in a previous frame.
+    rendered_by_plugin: bool,
+}
+
+impl RegisteredSpinner {
+    /// Renders the spinner if it was not updated during the given pass.
+    /// Returns false if the spinner should be unregistered.
+    fn update_if_missed(&mut self, ctx: &egui::Context, id: egui::Id, pass_nr: u64) -> bool {
+        if self.pass_nr == pass_nr {
+            return true;
+        }
+
+        let Some(link) = self.link.upgrade() else {
+            return false;
+        };
+
+        // The spinner was closed by the application or by one of its handles.
+        if !link.load(Ordering::Relaxed) || self.view.handle.peek_close_request() {
+            return false;
+        }
+
+        if !self.rendered_by_plugin {
+            log::warn!(
+                "The modal spinner {id:?} was not updated this frame and is rendered by the \
+                plugin instead. Make sure the update method of the spinner is called every frame."
+            );
+            self.rendered_by_plugin = true;
+        }
+
+        self.view.show(ctx, id);
+
+        true
+    }
+}
@@ -0,0 +1,37 @@
+//! The progress bar displayed below the spinner in determinate mode.
+
+use crate::style::OverlayStyle;
+use crate::utils::available_font_id;
+
+/// Gets the height of the progress bar.
+pub fn progress_bar_height(ui: &egui::Ui) -> f32 {
+    ui.spacing().interact_size.y
+}
+
+/// Adds a progress bar displaying the given progress between 0.0 and 1.0.
+///
+/// The percentage is only displayed inside the bar if the font of the
+/// text style is available, as egui would otherwise panic when laying out the text.
+pub fn ui_progress_bar(
+    ui: &mut egui::Ui,
+    style: &OverlayStyle,
+    progress: f32,
+    show_percentage: bool,
+) {
+    let mut progress_bar = egui::ProgressBar::new(progress)
+        .desired_width(style.progress_bar_width())
+        .desired_height(progress_bar_height(ui));
+
+    if show_percentage && available_font_id(ui, &style.text_style).is_some() {
+        let mut text = egui::RichText::new(format!("{:.0}%", progress * 100.0))
+            .text_style(style.text_style.clone());
+
+        if let Some(color) = style.text_color {
+            text = text.color(color);
+        }
+
+        progress_bar = progress_bar.text(text);
+    }
+
+    ui.add(progress_bar);
+}
@@ -0,0 +1,152 @@
+//! The list of steps displayed below the spinner for tasks with multiple stages.
+
+use crate::style::OverlayStyle;
+use crate::utils::{available_font_id, row_height};
+
+/// The state of a step of the task, see [`ModalSpinner::set_steps`].
+///
+/// [`ModalSpinner::set_steps`]: crate::ModalSpinner::set_steps
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StepState {
+    /// The step was not started yet.
+    Pending,
+    /// The step is currently executed. This is the first step that was not completed.
+    Active,
+    /// The step was completed.
+    Completed,
+}
+
+/// A single step of the task.
+#[derive(Debug, Clone)]
+pub struct Step {
+    pub label: String,
+    pub completed: bool,
+}
+
+/// A row of the displayed list: the label and the state of a step.
+pub type StepRow<'a> = (&'a str, StepState);
+
+/// Gets the rows displaying the given steps.
+pub fn step_rows(steps: &[Step]) -> Vec<StepRow<'_>> {
+    steps
+        .iter()
+        .zip(step_states(steps))
+        .map(|(step, state)| (step.label.as_str(), state))
+        .collect()
+}
+
+/// Gets the state of every step, in the order of the steps.
+pub fn step_states(steps: &[Step]) -> impl Iterator<Item = StepState> + '_ {
+    let active = steps.iter().position(|step| !step.completed);
+
+    steps.iter().enumerate().map(move |(i, step)| {
+        if step.completed {
+            StepState::Completed
+        } else if Some(i) == active {
+            StepState::Active
+        } else {
+            StepState::Pending
+        }
+    })
+}
+
+/// Gets the height of a list with the given number of rows.
+/// Returns `None` if there are no rows or the font of the text style is not available.
+pub fn steps_height(ui: &egui::Ui, style: &OverlayStyle, rows: usize) -> Option<f32> {
+    if rows == 0 {
+        return None;
+    }
+
+    let row_height = row_height(ui, &style.text_style)?;
+
+    #[allow(clippy::cast_precision_loss)]
+    let rows = rows as f32;
+
+    Some(rows.mul_add(row_height, (rows - 1.0) * ui.spacing().item_spacing.y))
+}
+
+/// Adds the list of steps, each with an icon reflecting its state.
+/// The list is centered horizontally, while the steps are aligned to its left edge.
+/// Nothing is added if the font of the text style is not available.
+pub fn ui_steps(ui: &mut egui::Ui, style: &OverlayStyle, rows: &[StepRow<'_>]) {
+    let Some(font_id) = available_font_id(ui, &style.text_style) else {
+        return;
+    };
+
+    let icon_size = ui.fonts(|f| f.row_height(&font_id));
+    let label_width = rows
+        .iter()
+        .map(|(label, _)| {
+            ui.fonts(|f| {
+                f.layout_no_wrap((*label).to_owned(), font_id.clone(), egui::Color32::WHITE)
+                    .rect
+                    .width()
+            })
+        })
+        .fold(0.0, f32::max);
+
+    let width = icon_size + ui.spacing().item_spacing.x + label_width;
+    let height = steps_height(ui, style, rows.len()).unwrap_or_default();
+
+    ui.allocate_ui_with_layout(
+        egui::vec2(width.min(ui.available_width()), height),
+        egui::Layout::top_down(egui::Align::Min),
+        |ui| ui_steps_grid(ui, style, rows, icon_size),
+    );
+}
+
+fn ui_steps_grid(ui: &mut egui::Ui, style: &OverlayStyle, rows: &[StepRow<'_>], icon_size: f32) {
+    egui::Grid::new(ui.id().with("steps"))
+        .num_columns(2)
+        .show(ui, |ui| {
+            for &(label, state) in rows {
+                ui_step_icon(ui, style, state, icon_size);
+
+                if state == StepState::Pending {
+                    let style = OverlayStyle {
+                        text_color: Some(ui.visuals().weak_text_color()),
+                        ..style.clone()
+                    };
+                    style.ui_label(ui, label);
+                } else {
+                    style.ui_label(ui, label);
+                }
+
+                ui.end_row();
+            }
+        });
+}
+
+/// Adds the icon of a step: a check mark if it was completed, a spinner if it is active
+/// and an empty circle if it is pending.
+fn ui_step_icon(ui: &mut egui::Ui, style: &OverlayStyle, state: StepState, size: f32) {
+    if state == StepState::Active {
+        ui.add(
+            egui::Spinner::new()
+                .size(size)
+                .color(style.spinner_color(ui.visuals())),
+        );
+        return;
+    }
+
+    let (rect, _) = ui.allocate_exact_size(egui::Vec2::splat(size), egui::Sense::hover());
+    let rect = rect.shrink(size / 6.0);
+    let color = style
+        .text_color
+        .unwrap_or_else(|| ui.visuals().text_color());
+
+    if state == StepState::Completed {
+        let stroke = egui::Stroke::new(size / 8.0, color);
+        let points = vec![
+            egui::pos2(rect.left(), rect.center().y),
+            egui::pos2(rect.center().x - rect.width() / 8.0, rect.bottom()),
+            egui::pos2(rect.right(), rect.top()),
+        ];
+
+        ui.painter().add(egui::Shape::line(points, stroke));
+    } else {
+        let stroke = egui::Stroke::new(size / 12.0, ui.visuals().weak_text_color());
+        ui.painter()
+            .circle_stroke(rect.center(), rect.width() / 2.0, stroke);
+    }
+}
@@ -0,0 +1,370 @@
+//! Visual configuration of the modal overlay.
+
+use crate::utils::{available_font_id, is_positive_finite};
+
+/// The default width of the progress bar.
+const DEFAULT_PROGRESS_BAR_WIDTH: f32 = 200.0;
+
+/// The animation of the spinner, see [`ModalSpinner::spinner_style`].
+///
+/// [`ModalSpinner::spinner_style`]: crate::ModalSpinner::spinner_style
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SpinnerStyle {
+    /// The rotating arc of the default egui spinner.
+    #[default]
+    Circle,
+    /// Three dots growing and shrinking one after another.
+    Dots,
+    /// Four vertical bars rising and falling one after another.
+    Bars,
+    /// An arc rotating on top of a faint circular track.
+    Ring,
+    /// A dot with a ring expanding from it and fading out.
+    Pulse,
+}
+
+impl SpinnerStyle {
+    /// All available spinner styles.
+    pub const ALL: [Self; 5] = [
+        Self::Circle,
+        Self::Dots,
+        Self::Bars,
+        Self::Ring,
+        Self::Pulse,
+    ];
+}
+
+/// Bundles the visual configuration of the modal overlay.
+///
+/// The style can be set when creating the spinner using [`ModalSpinner::overlay_style`],
+/// or swapped at runtime using [`ModalSpinner::set_style`], for example by a theme editor.
+/// Values that are `None` are derived from the current egui style every frame.
+///
+/// [`ModalSpinner::overlay_style`]: crate::ModalSpinner::overlay_style
+/// [`ModalSpinner::set_style`]: crate::ModalSpinner::set_style
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct OverlayStyle {
+    /// The fill color of the modal background.
+    /// If None, a default based on the current theme is used.
+    pub fill_color: Option<egui::Color32>,
+    /// The corner rounding of the modal background.
+    pub rounding: egui::Rounding,
+    /// The size of the spinner. If None, the interact size of the current style is used.
+    /// Values that are not a finite number greater than zero are ignored.
+    pub spinner_size: Option<f32>,
+    /// The vertical space between the elements stacked in the modal, like the spinner,
+    /// the labels and the additional content.
+    /// If None, the item spacing of the current style is used.
+    /// Values that are not a finite number greater than or equal to zero are ignored.
+    pub item_spacing: Option<f32>,
+    /// The color of the spinner. If None, the foreground color of the light or dark theme is
+    /// used, depending on which is easier to see on top of the modal background.
+    pub spinner_color: Option<egui::Color32>,
+    /// The animation of the spinner.
+    pub spinner_style: SpinnerStyle,
+    /// If the accent color of the egui visuals, the fill color of selections, should be used
+    /// for the spinner, if no spinner color is set.
+    /// The progress bar always uses the accent color.
+    pub accent_from_visuals: bool,
+    /// The text style of the labels displayed below the spinner, like the elapsed time.
+    pub text_style: egui::TextStyle,
+    /// The color of the labels displayed below the spinner.
+    /// If None, the default text color of the current style is used.
+    pub text_color: Option<egui::Color32>,
+    /// The maximum width of the labels displayed below the spinner. Longer labels are
+    /// truncated with an ellipsis and show the full text when hovered.
+    /// If None, the labels are only truncated at the edge of the screen.
+    pub max_label_width: Option<f32>,
+    /// The width of the progress bar displayed below the spinner when a progress is set.
+    /// Values that are not a finite number greater than zero are ignored.
+    pub progress_bar_width: f32,
+}
+
+impl Default for OverlayStyle {
+    fn default() -> Self {
+        Self {
+            fill_color: None,
+            rounding: egui::Rounding::ZERO,
+            spinner_size: None,
+            item_spacing: None,
+            spinner_color: None,
+            spinner_style: SpinnerStyle::Circle,
+            accent_from_visuals: false,
+            text_style: egui::TextStyle::Body,
+            text_color: None,
+            max_label_width: None,
+            progress_bar_width: DEFAULT_PROGRESS_BAR_WIDTH,
+        }
+    }
+}
+
+impl From<&egui::Visuals> for OverlayStyle {
+    /// Creates a style with the colors derived from the given egui visuals.
+    ///
+    /// The colors are the same that are used when they are not set,
+    /// but they no longer change when the egui visuals change.
+    fn from(visuals: &egui::Visuals) -> Self {
+        let default = Self::default();
+
+        Self {
+            fill_color: Some(default.fill_color(visuals)),
+            spinner_color: Some(default.spinner_color(visuals)),
+            text_color: Some(visuals.text_color()),
+            ..default
+        }
+    }
+}
+
+impl From<&egui::Spacing> for OverlayStyle {
+    /// Creates a style with the sizes derived from the given egui spacing.
+    fn from(spacing: &egui::Spacing) -> Self {
+        let default = Self::default();
+
+        Self {
+            spinner_size: Some(default.spinner_size(spacing)),
+            item_spacing: Some(default.item_spacing(spacing)),
+            ..default
+        }
+    }
+}
+
+impl From<&egui::Style> for OverlayStyle {
+    /// Creates a style with the colors and sizes derived from the given egui style.
+    /// See the conversions from `egui::Visuals` and `egui::Spacing`.
+    fn from(style: &egui::Style) -> Self {
+        let spacing = Self::from(&style.spacing);
+
+        Self {
+            spinner_size: spacing.spinner_size,
+            item_spacing: spacing.item_spacing,
+            ..Self::from(&style.visuals)
+        }
+    }
+}
+
+impl OverlayStyle {
+    /// Gets the fill color of the modal background for the given egui visuals.
+    pub(crate) fn fill_color(&self, visuals: &egui::Visuals) -> egui::Color32 {
+        self.fill_color.unwrap_or_else(|| {
+            if visuals.dark_mode {
+                egui::Color32::from_black_alpha(120)
+            } else {
+                egui::Color32::from_white_alpha(40)
+            }
+        })
+    }
+
+    /// Gets the color of the spinner for the given egui visuals.
+    pub(crate) fn spinner_color(&self, visuals: &egui::Visuals) -> egui::Color32 {
+        if let Some(color) = self.spinner_color {
+            return color;
+        }
+
+        if self.accent_from_visuals {
+            return visuals.selection.bg_fill;
+        }
+
+        // The modal background is usually translucent, so blend it with the panel fill to get
+        // an estimate of the color the spinner is actually displayed on.
+        let fill = egui::Rgba::from(self.fill_color(visuals));
+        let background = fill + egui::Rgba::from(visuals.panel_fill) * (1.0 - fill.a());
+
+        let visuals = if background.intensity() > 0.5 {
+            egui::Visuals::light()
+        } else {
+            egui::Visuals::dark()
+        };
+
+        visuals.widgets.inactive.fg_stroke.color
+    }
+
+    /// Gets the size of the spinner for the given egui spacing.
+    pub(crate) fn spinner_size(&self, spacing: &egui::Spacing) -> f32 {
+        self.spinner_size
+            .filter(|size| is_positive_finite(*size))
+            .unwrap_or(spacing.interact_size.y)
+    }
+
+    /// Gets the vertical space between the elements of the modal for the given egui spacing.
+    pub(crate) fn item_spacing(&self, spacing: &egui::Spacing) -> f32 {
+        self.item_spacing
+            .filter(|item_spacing| item_spacing.is_finite() && *item_spacing >= 0.0)
+            .unwrap_or(spacing.item_spacing.y)
+    }
+
+    /// Gets the width of the progress bar.
+    pub(crate) const fn progress_bar_width(&self) -> f32 {
+        if is_positive_finite(self.progress_bar_width) {
+            self.progress_bar_width
+        } else {
+            DEFAULT_PROGRESS_BAR_WIDTH
+        }
+    }
+
+    /// Adds a label displayed below the spinner.
+    /// The label is truncated if it is wider than the maximum label width.
+    /// Nothing is added if the font of the text style is not available.
+    pub(crate) fn ui_label(&self, ui: &mut egui::Ui, text: impl Into<String>) {
+        if available_font_id(ui, &self.text_style).is_none() {
+            return;
+        }
+
+        let mut text = egui::RichText::new(text).text_style(self.text_style.clone());
+
+        if let Some(color) = self.text_color {
+            text = text.color(color);
+        }
+
+        let available_width = ui.available_width();
+        let max_width = self
+            .max_label_width
+            .filter(|width| is_positive_finite(*width))
+            .map_or(available_width, |width| width.min(available_width));
+
+        ui.scope(|ui| {
+            ui.set_max_width(max_width);
+            ui.add(egui::Label::new(text).truncate());
+        });
+    }
+}
+
+/// Shows a panel to edit all options of the given overlay style at runtime.
+///
+/// This is useful when integrating the spinner into a custom theme.
+/// To tweak the style while the spinner is open, show the editor on a layer above the
+/// modal, for example inside a window with `egui::Order::Tooltip`.
+/// Returns true if the style was changed.
+#[cfg(feature = "debug-tools")]
+pub fn style_editor_ui(style: &mut OverlayStyle, ui: &mut egui::Ui) -> bool {
+    let previous = style.clone();
+
+    egui::Grid::new("egui_modal_spinner_style_editor")
+        .num_columns(2)
+        .striped(true)
+        .show(ui, |ui| {
+            ui.label("Fill color");
+            ui_edit_optional(
+                ui,
+                &mut style.fill_color,
+                egui::Color32::from_black_alpha(120),
+                |ui, color| {
+                    ui.color_edit_button_srgba(color);
+                },
+            );
+            ui.end_row();
+
+            ui.label("Rounding");
+            ui.horizontal(|ui| {
+                let rounding = &mut style.rounding;
+
+                for corner in [
+                    &mut rounding.nw,
+                    &mut rounding.ne,
+                    &mut rounding.sw,
+                    &mut rounding.se,
+                ] {
+                    ui.add(egui::DragValue::new(corner).range(0.0..=100.0));
+                }
+            });
+            ui.end_row();
+
+            let interact_size = ui.spacing().interact_size.y;
+            ui.label("Spinner size");
+            ui_edit_optional(ui, &mut style.spinner_size, interact_size, |ui, size| {
+                ui.add(egui::Slider::new(size, 1.0..=200.0));
+            });
+            ui.end_row();
+
+            let item_spacing = ui.spacing().item_spacing.y;
+            ui.label("Item spacing");
+            ui_edit_optional(ui, &mut style.item_spacing, item_spacing, |ui, spacing| {
+                ui.add(egui::Slider::new(spacing, 0.0..=100.0));
+            });
+            ui.end_row();
+
+            let text_color = ui.visuals().strong_text_color();
+            ui.label("Spinner color");
+            ui_edit_optional(ui, &mut style.spinner_color, text_color, |ui, color| {
+                ui.color_edit_button_srgba(color);
+            });
+            ui.end_row();
+
+            ui.label("Spinner style");
+            egui::ComboBox::from_id_salt("egui_modal_spinner_spinner_style")
+                .selected_text(format!("{:?}", style.spinner_style))
+                .show_ui(ui, |ui| {
+                    for spinner_style in SpinnerStyle::ALL {
+                        let label = format!("{spinner_style:?}");
+                        ui.selectable_value(&mut style.spinner_style, spinner_style, label);
+                    }
+                });
+            ui.end_row();
+
+            ui.label("Accent from visuals");
+            ui.checkbox(&mut style.accent_from_visuals, "");
+            ui.end_row();
+
+            ui.label("Text style");
+            let text_styles = ui.style().text_styles.keys().cloned().collect::<Vec<_>>();
+            egui::ComboBox::from_id_salt("egui_modal_spinner_text_style")
+                .selected_text(style.text_style.to_string())
+                .show_ui(ui, |ui| {
+                    for text_style in text_styles {
+                        let label = text_style.to_string();
+                        ui.selectable_value(&mut style.text_style, text_style, label);
+                    }
+                });
+            ui.end_row();
+
+            let text_color = ui.visuals().text_color();
+            ui.label("Text color");
+            ui_edit_optional(ui, &mut style.text_color, text_color, |ui, color| {
+                ui.color_edit_button_srgba(color);
+            });
+            ui.end_row();
+
+            ui.label("Max label width");
+            ui_edit_optional(ui, &mut style.max_label_width, 400.0, |ui, width| {
+                ui.add(egui::Slider::new(width, 10.0..=1000.0));
+            });
+            ui.end_row();
+
+            ui.label("Progress bar width");
+            ui.add(egui::Slider::new(
+                &mut style.progress_bar_width,
+                10.0..=1000.0,
+            ));
+            ui.end_row();
+        });
+
+    if ui.button("Reset").clicked() {
+        *style = OverlayStyle::default();
+    }
+
+    *style != previous
+}
+
+/// Shows a checkbox to enable a custom value, and the editor for the value if it is enabled.
+/// `default` is used as the initial value when the checkbox is enabled.
+#[cfg(feature = "debug-tools")]
+fn ui_edit_optional<T>(
+    ui: &mut egui::Ui,
+    value: &mut Option<T>,
+    default: T,
+    edit: impl FnOnce(&mut egui::Ui, &mut T),
+) {
+    ui.horizontal(|ui| {
+        let mut custom = value.is_some();
+
+        if ui.checkbox(&mut custom, "Custom").changed() {
+            *value = custom.then_some(default);
+        }
+
+        if let Some(value) = value {
+            edit(ui, value);
+        }
+    });
+}
@@ -0,0 +1,231 @@
+//! Tasks executed on the UI thread while the spinner is open.
+
+use std::fmt;
+use std::time::Duration;
+
+use web_time::Instant;
+
+use crate::utils::is_positive_finite;
+use crate::ConfigError;
+
+/// The result of a single step of a [`CooperativeTask`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum TaskStep<T> {
+    /// The task has more work to do and should be stepped again.
+    Continue,
+    /// The task has more work to do and reports its progress between 0.0 and 1.0.
+    /// The progress is displayed by [`ModalSpinner::update_with_task`].
+    ///
+    /// [`ModalSpinner::update_with_task`]: crate::ModalSpinner::update_with_task
+    Progress(f32),
+    /// The task is finished with the given output.
+    Done(T),
+}
+
+/// A task that is executed on the UI thread in small time slices.
+///
+/// This is intended for work that can not be moved to a background thread,
+/// for example operations bound to the graphics context. The task consists of a step
+/// function that performs a small unit of work each time it is called.
+/// Every frame, the step function is called repeatedly until the configured slice
+/// duration is used up, so the UI stays responsive while the task is executed.
+///
+/// Use [`ModalSpinner::update_with_task`] to execute the task while the spinner is open.
+/// The progress reported with [`TaskStep::Progress`] is displayed by the spinner.
+///
+/// [`ModalSpinner::update_with_task`]: crate::ModalSpinner::update_with_task
+///
+/// # Example
+/// ```rust
+/// use egui_modal_spinner::{CooperativeTask, TaskStep};
+///
+/// let mut items = (0..1000).collect::<Vec<u32>>();
+/// let mut sum = 0;
+///
+/// let task = CooperativeTask::new(move || match items.pop() {
+///     Some(item) => {
+///         sum += item;
+///         TaskStep::Progress(1.0 - items.len() as f32 / 1000.0)
+///     }
+///     None => TaskStep::Done(sum),
+/// });
+/// ```
+pub struct CooperativeTask<T> {
+    /// The step function performing a single unit of work.
+    step: Box<dyn FnMut() -> TaskStep<T>>,
+    /// The maximum time that is spent executing the task per frame.
+    slice_duration: Duration,
+    /// The frame time the slice duration is tuned to. None if the slice duration is fixed.
+    target_frame_time: Option<Duration>,
+    /// If the frame budget consumed by the task should be displayed below the spinner.
+    pub(crate) show_frame_budget: bool,
+    /// The time actually spent executing the last slice.
+    last_slice_time: Duration,
+    /// The last progress reported by the step function.
+    progress: Option<f32>,
+    /// If the task returned `TaskStep::Done`.
+    finished: bool,
+}
+
+impl<T> fmt::Debug for CooperativeTask<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CooperativeTask")
+            .field("slice_duration", &self.slice_duration)
+            .field("target_frame_time", &self.target_frame_time)
+            .field("show_frame_budget", &self.show_frame_budget)
+            .field("last_slice_time", &self.last_slice_time)
+            .field("progress", &self.progress)
+            .field("finished", &self.finished)
+            .finish()
+    }
+}
+
+/// Creation methods
+impl<T> CooperativeTask<T> {
+    /// Creates a new cooperative task from the given step function.
+    pub fn new(step: impl FnMut() -> TaskStep<T> + 'static) -> Self {
+        Self {
+            step: Box::new(step),
+            slice_duration: Duration::from_millis(8),
+            target_frame_time: None,
+            show_frame_budget: false,
+            last_slice_time: Duration::ZERO,
+            progress: None,
+            finished: false,
+        }
+    }
+
+    /// Sets the maximum time that is spent executing the task per frame.
+    ///
+    /// The step function is always called at least once per frame, so a single step should
+    /// be considerably shorter than the slice duration. Defaults to 8 ms.
+    pub const fn slice_duration(mut self, duration: Duration) -> Self {
+        self.slice_duration = duration;
+        self
+    }
+
+    /// Automatically tunes the slice duration to hold the given frame rate.
+    ///
+    /// When executed with [`ModalSpinner::update_with_task`], the slice duration is adjusted
+    /// every frame based on the measured frame time, so that the time left by the rest of the
+    /// frame is used for the task. The configured slice duration is used as the initial value.
+    ///
+    /// The frame rate is ignored if it is not a finite number greater than zero, or if it is
+    /// so small that its frame time can not be represented as a duration.
+    /// Use [`CooperativeTask::try_target_fps`] to detect invalid frame rates.
+    ///
+    /// [`ModalSpinner::update_with_task`]: crate::ModalSpinner::update_with_task
+    pub fn target_fps(mut self, fps: f32) -> Self {
+        if let Some(frame_time) = frame_time(fps) {
+            self.target_frame_time = Some(frame_time);
+        }
+
+        self
+    }
+
+    /// Automatically tunes the slice duration to hold the given frame rate.
+    /// See [`CooperativeTask::target_fps`] for more information.
+    ///
+    /// Returns an error if the frame rate is not a finite number greater than zero, or if
+    /// its frame time can not be represented as a duration.
+    pub fn try_target_fps(self, fps: f32) -> Result<Self, ConfigError> {
+        if frame_time(fps).is_none() {
+            return Err(ConfigError::InvalidFrameRate(fps));
+        }
+
+        Ok(self.target_fps(fps))
+    }
+
+    /// If the frame budget consumed by the task should be displayed below the spinner.
+    pub const fn show_frame_budget(mut self, show_frame_budget: bool) -> Self {
+        self.show_frame_budget = show_frame_budget;
+        self
+    }
+}
+
+/// Getter and setter
+impl<T> CooperativeTask<T> {
+    /// Checks if the task is finished.
+    pub const fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Gets the maximum time that is currently spent executing the task per frame.
+    /// This changes over time if [`CooperativeTask::target_fps`] is used.
+    pub const fn current_slice_duration(&self) -> Duration {
+        self.slice_duration
+    }
+
+    /// Gets the time actually spent executing the last slice.
+    pub const fn last_slice_time(&self) -> Duration {
+        self.last_slice_time
+    }
+
+    /// Gets the last progress reported with [`TaskStep::Progress`], between 0.0 and 1.0.
+    /// Returns None if the task did not report a progress yet.
+    pub const fn progress(&self) -> Option<f32> {
+        self.progress
+    }
+}
+
+/// Implementation methods
+impl<T> CooperativeTask<T> {
+    /// Executes the task until the slice duration is used up or the task is done.
+    ///
+    /// Returns the output of the task if it finished during this slice.
+    /// Does nothing if the task is already finished.
+    pub fn run_slice(&mut self) -> Option<T> {
+        if self.finished {
+            return None;
+        }
+
+        let start = Instant::now();
+
+        loop {
+            let step = (self.step)();
+            self.last_slice_time = start.elapsed();
+
+            match step {
+                TaskStep::Continue => {}
+                TaskStep::Progress(progress) => {
+                    if !progress.is_nan() {
+                        self.progress = Some(progress.clamp(0.0, 1.0));
+                    }
+                }
+                TaskStep::Done(output) => {
+                    self.finished = true;
+                    return Some(output);
+                }
+            }
+
+            if self.last_slice_time >= self.slice_duration {
+                return None;
+            }
+        }
+    }
+
+    /// Adjusts the slice duration so that the target frame time is held.
+    /// `frame_time` is the measured time of the last frame, including the last slice.
+    pub(crate) fn tune_slice_duration(&mut self, frame_time: Duration) {
+        let Some(target) = self.target_frame_time else {
+            return;
+        };
+
+        // The time of the frame not spent executing the task, like rendering the UI.
+        let remaining_frame_time = frame_time.saturating_sub(self.last_slice_time);
+        let ideal = target.saturating_sub(remaining_frame_time);
+
+        // Only move halfway to the ideal duration to smooth out noisy frame times.
+        self.slice_duration = self.slice_duration.saturating_add(ideal) / 2;
+    }
+}
+
+/// Gets the time of a single frame at the given frame rate.
+/// Returns `None` if the frame rate is invalid or the frame time is out of range.
+fn frame_time(fps: f32) -> Option<Duration> {
+    if !is_positive_finite(fps) {
+        return None;
+    }
+
+    Duration::try_from_secs_f32(1.0 / fps).ok()
+}
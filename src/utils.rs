@@ -0,0 +1,45 @@
+/// Checks if the given value is a finite number greater than zero.
+pub const fn is_positive_finite(value: f32) -> bool {
+    value.is_finite() && value > 0.0
+}
+
+/// Gets the font of the given text style.
+/// Returns `None` if the text style or its font family is not available.
+pub fn available_font_id(ui: &egui::Ui, text_style: &egui::TextStyle) -> Option<egui::FontId> {
+    let font_id = ui.style().text_styles.get(text_style)?.clone();
+
+    ui.fonts(|f| f.families().contains(&font_id.family))
+        .then_some(font_id)
+}
+
+/// Gets the row height of the given text style.
+/// Returns `None` if the text style or its font family is not available.
+pub fn row_height(ui: &egui::Ui, text_style: &egui::TextStyle) -> Option<f32> {
+    let font_id = available_font_id(ui, text_style)?;
+    Some(ui.fonts(|f| f.row_height(&font_id)))
+}
+
+/// A clonable callback stored in the spinner, called with a reference to the given argument.
+pub struct Callback<A: ?Sized, R = ()>(std::sync::Arc<dyn Fn(&A) -> R + Send + Sync>);
+
+impl<A: ?Sized, R> Callback<A, R> {
+    pub fn new(callback: impl Fn(&A) -> R + Send + Sync + 'static) -> Self {
+        Self(std::sync::Arc::new(callback))
+    }
+
+    pub fn call(&self, arg: &A) -> R {
+        (self.0)(arg)
+    }
+}
+
+impl<A: ?Sized, R> Clone for Callback<A, R> {
+    fn clone(&self) -> Self {
+        Self(std::sync::Arc::clone(&self.0))
+    }
+}
+
+impl<A: ?Sized, R> std::fmt::Debug for Callback<A, R> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Callback")
+    }
+}
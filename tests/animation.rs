@@ -0,0 +1,74 @@
+//! Tests for playing image sequences as the spinner.
+
+use std::time::Duration;
+
+use egui_modal_spinner::{AnimatedImage, ModalSpinner};
+
+fn load_frames(ctx: &egui::Context, count: usize) -> Vec<egui::TextureHandle> {
+    (0..count)
+        .map(|i| {
+            ctx.load_texture(
+                format!("frame_{i}"),
+                egui::ColorImage::new([4, 4], egui::Color32::RED),
+                egui::TextureOptions::LINEAR,
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn frames_are_played_in_a_loop() {
+    let ctx = egui::Context::default();
+    let textures = load_frames(&ctx, 3);
+    let durations = [10, 0, 30].map(Duration::from_millis);
+
+    let animation = AnimatedImage::new(textures.into_iter().zip(durations));
+    assert_eq!(animation.frame_count(), 2);
+    assert_eq!(animation.loop_duration(), Duration::from_millis(40));
+
+    let frame_at = |millis| animation.frame_at(Duration::from_millis(millis));
+    assert_eq!(frame_at(0), Some((0, Duration::from_millis(10))));
+    assert_eq!(frame_at(15), Some((1, Duration::from_millis(25))));
+    assert_eq!(frame_at(45), Some((0, Duration::from_millis(5))));
+
+    assert_eq!(AnimatedImage::new([]).frame_at(Duration::ZERO), None);
+}
+
+#[test]
+fn long_frame_durations_do_not_overflow() {
+    let ctx = egui::Context::default();
+    let textures = load_frames(&ctx, 2);
+
+    let animation = AnimatedImage::from_textures(textures, Duration::MAX);
+    assert_eq!(animation.loop_duration(), Duration::MAX);
+    assert_eq!(
+        animation
+            .frame_at(Duration::from_secs(1))
+            .map(|(frame, _)| frame),
+        Some(0)
+    );
+}
+
+#[test]
+fn current_frame_is_painted_as_spinner() {
+    let ctx = egui::Context::default();
+    let textures = load_frames(&ctx, 2);
+    let first = textures.first().map(egui::TextureHandle::id);
+
+    let mut spinner =
+        ModalSpinner::new()
+            .fade_in(false)
+            .custom_spinner(AnimatedImage::from_textures(
+                textures,
+                Duration::from_secs(30),
+            ));
+    spinner.open();
+
+    let output = ctx.run(egui::RawInput::default(), |ctx| spinner.update(ctx));
+
+    let painted = output.shapes.iter().any(|shape| match &shape.shape {
+        egui::Shape::Rect(rect) => Some(rect.fill_texture_id) == first,
+        _ => false,
+    });
+    assert!(painted);
+}
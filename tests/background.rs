@@ -0,0 +1,109 @@
+//! Tests for moving the task to the background using the background button.
+
+use egui_modal_spinner::ModalSpinner;
+
+mod common;
+
+use common::{Harness, SCREEN_SIZE};
+
+fn new_harness() -> Harness {
+    Harness::new(
+        ModalSpinner::new()
+            .fade_in(false)
+            .elapsed_label("Elapsed")
+            .show_background_button(true)
+            .background_label("Hide"),
+    )
+}
+
+/// Checks if the modal background was painted in the last frame.
+fn modal_painted(harness: &Harness) -> bool {
+    harness
+        .output
+        .shapes
+        .iter()
+        .any(|shape| match &shape.shape {
+            egui::Shape::Rect(rect) => rect.rect.width() >= SCREEN_SIZE.x,
+            _ => false,
+        })
+}
+
+#[test]
+fn background_button_moves_spinner_to_corner() {
+    let mut harness = new_harness();
+    assert!(modal_painted(&harness));
+    assert!(harness.spinner.should_disable_ui());
+
+    let pos = harness.text_rect("Hide").map(|rect| rect.center());
+    harness.click(pos.unwrap_or_default());
+
+    // A new area is only displayed after its first frame.
+    harness.run(Vec::new());
+    harness.run(Vec::new());
+
+    assert!(harness.spinner.is_open());
+    assert!(harness.spinner.is_in_background());
+    assert!(!harness.spinner.should_disable_ui());
+    assert!(!modal_painted(&harness));
+
+    // The elapsed time is displayed in the bottom right corner.
+    let elapsed = harness.text_rect("Elapsed").map(|rect| rect.center());
+    assert!(elapsed.is_some_and(|pos| pos.x > SCREEN_SIZE.x / 2.0 && pos.y > SCREEN_SIZE.y / 2.0));
+
+    harness.spinner.close();
+    assert!(!harness.spinner.should_disable_ui());
+
+    harness.spinner.open();
+    assert!(!harness.spinner.is_in_background());
+}
+
+#[test]
+fn non_modal_spinner_does_not_block_input() {
+    let mut harness = new_harness();
+    harness.spinner = ModalSpinner::new()
+        .modal(false)
+        .elapsed_label("Elapsed")
+        .indicator_corner(egui::Align2::LEFT_TOP);
+
+    harness.spinner.open();
+    harness.run(Vec::new());
+    harness.run(Vec::new());
+
+    assert!(harness.spinner.is_open());
+    assert!(!harness.spinner.should_disable_ui());
+    assert!(!modal_painted(&harness));
+
+    let elapsed = harness.text_rect("Elapsed").map(|rect| rect.center());
+    assert!(elapsed.is_some_and(|pos| pos.x < SCREEN_SIZE.x / 2.0 && pos.y < SCREEN_SIZE.y / 2.0));
+
+    // Errors are still displayed in the modal.
+    harness.spinner.fail("Connection lost");
+    harness.run(Vec::new());
+    assert!(modal_painted(&harness));
+    assert!(harness.spinner.should_disable_ui());
+}
+
+#[test]
+fn spinner_in_background_is_closed_with_result() {
+    for success in [true, false] {
+        let mut harness = new_harness();
+
+        let pos = harness.text_rect("Hide").map(|rect| rect.center());
+        harness.click(pos.unwrap_or_default());
+        assert!(harness.spinner.is_in_background());
+
+        if success {
+            harness.spinner.close_with_success();
+        } else {
+            harness.spinner.close_with_error();
+        }
+
+        // Without a time in the input, egui advances the time by 1/60 s per frame,
+        // so this exceeds the default completion duration of 600 ms.
+        for _ in 0..60 {
+            harness.run(Vec::new());
+        }
+
+        assert!(!harness.spinner.is_open());
+    }
+}
@@ -0,0 +1,134 @@
+//! Tests for the mini-map displaying the items of batch operations.
+
+use std::thread;
+
+use egui_modal_spinner::{ErrorAction, ItemState, ModalSpinner, SpinnerState};
+
+mod common;
+
+use common::Harness;
+
+/// Gets the number of cells painted by the mini-map in the last frame.
+fn cell_count(harness: &Harness) -> Option<usize> {
+    harness
+        .output
+        .shapes
+        .iter()
+        .find_map(|shape| match &shape.shape {
+            egui::Shape::Mesh(mesh) if mesh.texture_id == egui::TextureId::default() => {
+                Some(mesh.vertices.len() / 4)
+            }
+            _ => None,
+        })
+}
+
+#[test]
+fn items_are_reported_from_thread() {
+    let mut harness = Harness::closed(ModalSpinner::new().fade_in(false));
+    let handle = harness.spinner.handle();
+
+    harness.spinner.open();
+    harness.spinner.set_item_count(16);
+    harness.run(Vec::new());
+    assert_eq!(cell_count(&harness), Some(16));
+
+    let _ = thread::spawn(move || {
+        handle.complete_item(0);
+        handle.fail_item(1);
+        handle.complete_item(100);
+    })
+    .join();
+
+    assert_eq!(harness.spinner.item_state(0), Some(ItemState::Pending));
+
+    harness.run(Vec::new());
+    assert_eq!(harness.spinner.item_state(0), Some(ItemState::Completed));
+    assert_eq!(harness.spinner.item_state(1), Some(ItemState::Failed));
+    assert_eq!(harness.spinner.item_state(2), Some(ItemState::Pending));
+    assert_eq!(harness.spinner.item_state(100), None);
+
+    harness.spinner.clear_items();
+    harness.run(Vec::new());
+    assert_eq!(cell_count(&harness), None);
+}
+
+#[test]
+fn large_batches_share_cells() {
+    let mut harness = Harness::closed(ModalSpinner::new().fade_in(false));
+
+    harness.spinner.open();
+    harness.spinner.set_item_count(100_000);
+
+    harness.run(Vec::new());
+    let cells = cell_count(&harness);
+    assert!(cells.is_some_and(|cells| cells > 0 && cells < 100_000));
+}
+
+#[test]
+fn failures_are_summarized_when_closing() {
+    let mut harness = Harness::closed(ModalSpinner::new().fade_in(false));
+    let handle = harness.spinner.handle();
+
+    harness.spinner.open();
+    harness.spinner.set_item_count(4);
+    handle.fail_item_with_reason(1, "Corrupted tile");
+    handle.complete_item(2);
+    harness.spinner.fail_item(3);
+    harness.run(Vec::new());
+
+    assert_eq!(harness.spinner.item_state(1), Some(ItemState::Failed));
+    assert_eq!(harness.spinner.failures().len(), 2);
+    assert_eq!(
+        harness
+            .spinner
+            .failures()
+            .iter()
+            .find(|failure| failure.index == 1)
+            .and_then(|failure| failure.reason.as_deref()),
+        Some("Corrupted tile")
+    );
+
+    harness.spinner.close();
+    assert_eq!(harness.spinner.state(), &SpinnerState::Error);
+    assert_eq!(harness.spinner.error(), Some("2 items failed"));
+
+    // The fonts are only available after the first frame.
+    harness.run(Vec::new());
+    harness.run(Vec::new());
+    assert!(harness.text_rect("Details").is_some());
+
+    // The failures can still be retrieved after the summary was dismissed.
+    harness.spinner.close();
+    assert!(!harness.spinner.is_open());
+    assert_eq!(harness.spinner.take_error_action(), None::<ErrorAction>);
+    assert_eq!(harness.spinner.take_failures().len(), 2);
+
+    harness.spinner.open();
+    harness.spinner.fail_item(0);
+    harness.spinner.open();
+    assert!(harness.spinner.failures().is_empty());
+}
+
+#[test]
+fn failure_summary_can_be_disabled() {
+    let mut spinner = ModalSpinner::new().failure_summary(false);
+
+    spinner.open();
+    spinner.fail_item_with_reason(0, "Timeout");
+    spinner.close();
+
+    assert!(!spinner.is_open());
+    assert_eq!(spinner.failures().len(), 1);
+}
+
+#[test]
+fn only_first_placeholder_of_failure_label_is_replaced() {
+    let mut spinner = ModalSpinner::new().failure_label("{} items failed: {}");
+
+    spinner.open();
+    spinner.fail_item(0);
+    spinner.fail_item(1);
+    spinner.close();
+
+    assert_eq!(spinner.error(), Some("2 items failed: {}"));
+}
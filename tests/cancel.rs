@@ -0,0 +1,135 @@
+//! Tests for the cancel button displayed below the spinner.
+
+use std::time::Duration;
+
+use egui_modal_spinner::ModalSpinner;
+
+mod common;
+
+use common::Harness;
+
+const ESCAPE_HINT: &str = "Press Esc to cancel";
+
+fn new_harness() -> Harness {
+    Harness::new(
+        ModalSpinner::new()
+            .fade_in(false)
+            .show_cancel_button(true)
+            .cancel_label("Abort"),
+    )
+}
+
+#[test]
+fn cancel_button_is_not_displayed_by_default() {
+    let mut harness = new_harness();
+    harness.spinner = ModalSpinner::new().fade_in(false).cancel_label("Abort");
+
+    harness.spinner.open();
+    harness.run(Vec::new());
+
+    assert!(harness.text_rect("Abort").is_none());
+}
+
+#[test]
+fn clicking_cancel_button_requests_cancel() {
+    let mut harness = new_harness();
+    assert!(!harness.spinner.cancel_requested());
+
+    let pos = harness.text_rect("Abort").map(|rect| rect.center());
+    assert!(pos.is_some());
+
+    harness.click(pos.unwrap_or_default());
+
+    assert!(harness.spinner.cancel_requested());
+    assert!(harness.spinner.is_open());
+    assert!(harness.spinner.describe().ends_with("\ncancel: requested"));
+}
+
+#[test]
+fn clicking_beside_cancel_button_does_not_request_cancel() {
+    let mut harness = new_harness();
+
+    harness.click(egui::pos2(10.0, 10.0));
+
+    assert!(!harness.spinner.cancel_requested());
+}
+
+#[test]
+fn cancel_request_is_reset_when_opening() {
+    let mut harness = new_harness();
+
+    let pos = harness.text_rect("Abort").map(|rect| rect.center());
+    harness.click(pos.unwrap_or_default());
+    assert!(harness.spinner.cancel_requested());
+
+    harness.spinner.close();
+    harness.spinner.open();
+
+    assert!(!harness.spinner.cancel_requested());
+}
+
+#[test]
+fn acknowledged_cancel_closes_spinner() {
+    let mut harness = new_harness();
+    let handle = harness.spinner.handle();
+
+    let pos = harness.text_rect("Abort").map(|rect| rect.center());
+    harness.click(pos.unwrap_or_default());
+    harness.run(Vec::new());
+
+    // The button displays the cancelling label until the worker acknowledges the request.
+    assert!(handle.is_cancel_requested());
+    assert!(harness.text_rect("Abort").is_none());
+    assert!(harness.spinner.is_open());
+
+    handle.acknowledge_cancel();
+    harness.run(Vec::new());
+
+    assert!(harness.spinner.cancel_acknowledged());
+    assert!(!harness.spinner.is_open());
+
+    harness.spinner.open();
+    assert!(!handle.is_cancel_requested());
+    assert!(!harness.spinner.cancel_acknowledged());
+}
+
+#[test]
+fn escape_key_requests_cancel_after_hint() {
+    let mut harness = new_harness();
+    assert!(harness.text_rect(ESCAPE_HINT).is_none());
+
+    harness.spinner = ModalSpinner::new()
+        .fade_in(false)
+        .show_cancel_button(true)
+        .escape_hint(Duration::ZERO);
+    harness.spinner.open();
+    harness.run(Vec::new());
+    assert!(harness.text_rect(ESCAPE_HINT).is_some());
+
+    harness.run(vec![egui::Event::Key {
+        key: egui::Key::Escape,
+        physical_key: None,
+        pressed: true,
+        repeat: false,
+        modifiers: egui::Modifiers::NONE,
+    }]);
+    assert!(harness.spinner.cancel_requested());
+
+    harness.run(Vec::new());
+    assert!(harness.text_rect(ESCAPE_HINT).is_none());
+}
+
+#[test]
+fn escape_hint_is_displayed_after_delay() {
+    let mut harness = new_harness();
+    harness.spinner = ModalSpinner::new()
+        .fade_in(false)
+        .show_cancel_button(true)
+        .escape_hint(Duration::from_secs(30));
+
+    harness.spinner.open();
+    harness.run(Vec::new());
+
+    assert!(harness.text_rect(ESCAPE_HINT).is_none());
+    assert!(!harness.spinner.cancel_requested());
+}
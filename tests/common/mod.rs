@@ -0,0 +1,141 @@
+//! Headless fixture shared by the tests that simulate user input.
+//! Not every test uses every part of the fixture.
+#![allow(dead_code)]
+
+use egui_modal_spinner::ModalSpinner;
+
+pub const SCREEN_SIZE: egui::Vec2 = egui::vec2(800.0, 600.0);
+
+/// Headless harness running frames of a single spinner.
+/// The output of the last frame is kept, so the tests can extract the painted shapes.
+pub struct Harness {
+    pub ctx: egui::Context,
+    pub spinner: ModalSpinner,
+    pub output: egui::FullOutput,
+    /// The size of the screen of the next frames.
+    pub screen_size: egui::Vec2,
+    /// The time of the next frames. If None, egui advances the time by 1/60 s per frame.
+    pub time: Option<f64>,
+}
+
+impl Harness {
+    /// Creates a harness with the given spinner, without opening it or running a frame.
+    pub fn closed(spinner: ModalSpinner) -> Self {
+        Self {
+            ctx: egui::Context::default(),
+            spinner,
+            output: egui::FullOutput::default(),
+            screen_size: SCREEN_SIZE,
+            time: None,
+        }
+    }
+
+    /// Creates a harness with the given spinner and opens it.
+    pub fn new(spinner: ModalSpinner) -> Self {
+        let mut harness = Self::closed(spinner);
+
+        harness.spinner.open();
+
+        // The fonts are only available after the first frame.
+        harness.run(Vec::new());
+        harness.run(Vec::new());
+
+        harness
+    }
+
+    /// Runs a frame with the given events that updates the spinner.
+    pub fn run(&mut self, events: Vec<egui::Event>) {
+        self.run_ui(events, |ctx, spinner| spinner.update(ctx));
+    }
+
+    /// Runs a frame at the given time that updates the spinner.
+    pub fn run_at(&mut self, time: f64) {
+        self.time = Some(time);
+        self.run(Vec::new());
+    }
+
+    /// Runs a frame with the given events and UI, which receives the spinner to update.
+    pub fn run_ui(
+        &mut self,
+        events: Vec<egui::Event>,
+        mut ui: impl FnMut(&egui::Context, &mut ModalSpinner),
+    ) {
+        let input = egui::RawInput {
+            screen_rect: Some(egui::Rect::from_min_size(
+                egui::Pos2::ZERO,
+                self.screen_size,
+            )),
+            time: self.time,
+            events,
+            ..Default::default()
+        };
+
+        let ctx = self.ctx.clone();
+        self.output = ctx.run(input, |ctx| ui(ctx, &mut self.spinner));
+    }
+
+    /// Clicks the primary pointer button at the given position.
+    pub fn click(&mut self, pos: egui::Pos2) {
+        for events in click_events(pos, egui::PointerButton::Primary) {
+            self.run(events);
+        }
+    }
+
+    /// Gets the alpha of the modal background painted in the last frame, if it was painted.
+    pub fn modal_alpha(&self) -> Option<u8> {
+        self.output
+            .shapes
+            .iter()
+            .find_map(|shape| match &shape.shape {
+                egui::Shape::Rect(rect) if rect.rect.width() >= self.screen_size.x => {
+                    Some(rect.fill.a())
+                }
+                _ => None,
+            })
+    }
+
+    /// Gets the rect of the given text painted in the last frame.
+    pub fn text_rect(&self, label: &str) -> Option<egui::Rect> {
+        self.output
+            .shapes
+            .iter()
+            .find_map(|shape| match &shape.shape {
+                egui::Shape::Text(text) if text.galley.text() == label => {
+                    Some(text.galley.rect.translate(text.pos.to_vec2()))
+                }
+                _ => None,
+            })
+    }
+}
+
+/// Creates the input of a frame covering the whole screen.
+pub fn screen_input(events: Vec<egui::Event>) -> egui::RawInput {
+    egui::RawInput {
+        screen_rect: Some(egui::Rect::from_min_size(egui::Pos2::ZERO, SCREEN_SIZE)),
+        events,
+        ..Default::default()
+    }
+}
+
+/// Gets the events of the three frames of a click: moving the pointer to the position,
+/// pressing the button and releasing it.
+pub fn click_events(pos: egui::Pos2, button: egui::PointerButton) -> [Vec<egui::Event>; 3] {
+    [
+        vec![egui::Event::PointerMoved(pos)],
+        vec![pointer_button(pos, button, true)],
+        vec![pointer_button(pos, button, false)],
+    ]
+}
+
+pub const fn pointer_button(
+    pos: egui::Pos2,
+    button: egui::PointerButton,
+    pressed: bool,
+) -> egui::Event {
+    egui::Event::PointerButton {
+        pos,
+        button,
+        pressed,
+        modifiers: egui::Modifiers::NONE,
+    }
+}
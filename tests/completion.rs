@@ -0,0 +1,64 @@
+//! Tests for the completion animation displayed when closing the spinner with a result.
+
+use std::time::Duration;
+
+use egui_modal_spinner::ModalSpinner;
+
+mod common;
+
+use common::Harness;
+
+/// Gets the number of line segments painted in the given color in the last frame.
+fn line_count(harness: &Harness, color: egui::Color32) -> usize {
+    harness
+        .output
+        .shapes
+        .iter()
+        .filter(|shape| match &shape.shape {
+            egui::Shape::LineSegment { stroke, .. } => {
+                stroke.color == egui::epaint::ColorMode::Solid(color)
+            }
+            _ => false,
+        })
+        .count()
+}
+
+#[test]
+fn error_is_displayed_before_closing() {
+    let mut harness = Harness::closed(
+        ModalSpinner::new()
+            .fade_in(false)
+            .fade_out(false)
+            .completion_duration(Duration::from_millis(500)),
+    );
+    let error_color = harness.ctx.style().visuals.error_fg_color;
+
+    harness.spinner.open();
+    harness.run_at(0.0);
+    assert_eq!(line_count(&harness, error_color), 0);
+
+    harness.spinner.close_with_error();
+    assert!(harness.spinner.is_open());
+
+    // The first stroke of the cross is drawn during the first quarter.
+    harness.run_at(0.0);
+    assert_eq!(line_count(&harness, error_color), 0);
+    harness.run_at(0.1);
+    assert_eq!(line_count(&harness, error_color), 1);
+    harness.run_at(0.3);
+    assert_eq!(line_count(&harness, error_color), 2);
+    assert!(harness.spinner.is_open());
+
+    harness.run_at(0.5);
+    assert!(!harness.spinner.is_open());
+}
+
+#[test]
+fn success_is_not_displayed_if_modal_was_never_visible() {
+    let mut spinner = ModalSpinner::new().show_after(Duration::from_secs(1));
+
+    spinner.open();
+    spinner.close_with_success();
+
+    assert!(!spinner.is_open());
+}
@@ -0,0 +1,20 @@
+//! Tests for the configuration methods of the spinner.
+
+use egui_modal_spinner::{ConfigError, ModalSpinner};
+
+#[test]
+fn try_spinner_size_accepts_valid_size() {
+    assert!(ModalSpinner::new().try_spinner_size(40.0).is_ok());
+}
+
+#[test]
+fn try_spinner_size_rejects_invalid_sizes() {
+    for size in [0.0, -1.0, f32::INFINITY] {
+        assert_eq!(
+            ModalSpinner::new().try_spinner_size(size).err(),
+            Some(ConfigError::InvalidSize(size))
+        );
+    }
+
+    assert!(ModalSpinner::new().try_spinner_size(f32::NAN).is_err());
+}
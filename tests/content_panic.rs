@@ -0,0 +1,46 @@
+//! Tests for catching panics of the content closure.
+
+use egui_modal_spinner::ModalSpinner;
+
+#[test]
+fn content_panic_is_caught_and_reported() {
+    let ctx = egui::Context::default();
+    let mut spinner = ModalSpinner::new()
+        .fade_in(false)
+        .content_panic_label("Broken status");
+
+    spinner.open();
+
+    let broken = true;
+    let mut note_displayed = false;
+
+    // The fonts are only available after the first frame.
+    for _ in 0..2 {
+        let output = ctx.run(egui::RawInput::default(), |ctx| {
+            spinner.update_with_content(ctx, |ui| {
+                ui.label("Status");
+                assert!(!broken, "renderer broke");
+            });
+        });
+
+        note_displayed = output.shapes.iter().any(|shape| match &shape.shape {
+            egui::Shape::Text(text) => text.galley.text() == "Broken status",
+            _ => false,
+        });
+    }
+
+    assert!(note_displayed);
+    assert!(spinner.content_panicked());
+    assert_eq!(
+        spinner.take_content_panic().as_deref(),
+        Some("renderer broke")
+    );
+    assert_eq!(spinner.take_content_panic(), None);
+
+    let _ = ctx.run(egui::RawInput::default(), |ctx| {
+        spinner.update_with_content(ctx, |ui| {
+            ui.label("Status");
+        });
+    });
+    assert!(!spinner.content_panicked());
+}
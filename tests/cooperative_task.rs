@@ -0,0 +1,199 @@
+//! Tests for executing cooperative tasks on the UI thread.
+
+use std::time::Duration;
+
+use egui_modal_spinner::{ConfigError, CooperativeTask, ModalSpinner, TaskStep};
+
+/// Creates a task that requires the given number of steps to finish.
+fn counting_task(steps: u32) -> CooperativeTask<u32> {
+    let mut count = 0;
+
+    CooperativeTask::new(move || {
+        count += 1;
+
+        if count == steps {
+            TaskStep::Done(count)
+        } else {
+            TaskStep::Continue
+        }
+    })
+}
+
+#[test]
+fn task_finishes_within_single_slice() {
+    let mut task = counting_task(100).slice_duration(Duration::from_secs(10));
+
+    assert_eq!(task.run_slice(), Some(100));
+    assert!(task.is_finished());
+    assert_eq!(task.run_slice(), None);
+}
+
+#[test]
+fn task_is_executed_at_least_one_step_per_slice() {
+    let mut task = counting_task(3).slice_duration(Duration::ZERO);
+
+    assert_eq!(task.run_slice(), None);
+    assert_eq!(task.run_slice(), None);
+    assert_eq!(task.run_slice(), Some(3));
+}
+
+#[test]
+fn spinner_closes_when_task_is_done() {
+    let ctx = egui::Context::default();
+    let mut spinner = ModalSpinner::new();
+    let mut task = counting_task(2).slice_duration(Duration::ZERO);
+
+    let mut run = |spinner: &mut ModalSpinner| {
+        let mut output = None;
+        let _ = ctx.run(egui::RawInput::default(), |ctx| {
+            output = spinner.update_with_task(ctx, &mut task);
+        });
+        output
+    };
+
+    // The task is not executed while the spinner is closed.
+    assert_eq!(run(&mut spinner), None);
+
+    spinner.open();
+    assert_eq!(run(&mut spinner), None);
+    assert!(spinner.is_open());
+
+    assert_eq!(run(&mut spinner), Some(2));
+    assert!(!spinner.is_open());
+}
+
+#[test]
+fn task_progress_is_displayed_by_spinner() {
+    let ctx = egui::Context::default();
+    let mut spinner = ModalSpinner::new();
+    let mut steps = [TaskStep::Progress(0.25), TaskStep::Continue].into_iter();
+    let mut task = CooperativeTask::new(move || steps.next().unwrap_or(TaskStep::Done(())))
+        .slice_duration(Duration::ZERO);
+
+    spinner.open();
+
+    let mut run = |spinner: &mut ModalSpinner| {
+        let _ = ctx.run(egui::RawInput::default(), |ctx| {
+            let _ = spinner.update_with_task(ctx, &mut task);
+        });
+    };
+
+    run(&mut spinner);
+    assert_eq!(spinner.progress(), Some(0.25));
+
+    // Steps without a progress keep the last reported progress.
+    run(&mut spinner);
+    assert_eq!(spinner.progress(), Some(0.25));
+}
+
+#[test]
+fn slice_duration_is_tuned_to_target_fps() {
+    let ctx = egui::Context::default();
+    let mut spinner = ModalSpinner::new();
+    let mut task = counting_task(u32::MAX)
+        .slice_duration(Duration::from_millis(10))
+        .target_fps(50.0);
+
+    spinner.open();
+
+    let mut run = |time: f64, task: &mut CooperativeTask<u32>| {
+        let input = egui::RawInput {
+            time: Some(time),
+            ..Default::default()
+        };
+
+        let _ = ctx.run(input, |ctx| {
+            let _ = spinner.update_with_task(ctx, task);
+        });
+    };
+
+    run(0.0, &mut task);
+
+    // A slow frame of 100 ms reduces the slice duration.
+    run(0.1, &mut task);
+    assert!(task.current_slice_duration() < Duration::from_millis(10));
+
+    // Fast frames increase the slice duration towards the 20 ms frame budget.
+    let mut time = 0.1;
+    for _ in 0..10 {
+        time += task.last_slice_time().as_secs_f64();
+        run(time, &mut task);
+    }
+
+    assert!(task.current_slice_duration() > Duration::from_millis(10));
+    assert!(task.current_slice_duration() <= Duration::from_millis(20));
+}
+
+#[test]
+fn invalid_target_fps_is_ignored() {
+    for fps in [0.0, -1.0, f32::INFINITY, f32::NAN, 1e-30] {
+        let mut task = counting_task(u32::MAX)
+            .slice_duration(Duration::from_millis(10))
+            .target_fps(fps);
+
+        let ctx = egui::Context::default();
+        let mut spinner = ModalSpinner::new();
+        spinner.open();
+
+        let _ = ctx.run(egui::RawInput::default(), |ctx| {
+            let _ = spinner.update_with_task(ctx, &mut task);
+        });
+
+        // Without a target frame time, the slice duration is not tuned.
+        assert_eq!(task.current_slice_duration(), Duration::from_millis(10));
+    }
+}
+
+#[test]
+fn try_target_fps_rejects_invalid_frame_rates() {
+    assert!(counting_task(1).try_target_fps(60.0).is_ok());
+
+    for fps in [0.0, -1.0, f32::INFINITY, 1e-30] {
+        assert_eq!(
+            counting_task(1).try_target_fps(fps).err(),
+            Some(ConfigError::InvalidFrameRate(fps))
+        );
+    }
+
+    assert!(counting_task(1).try_target_fps(f32::NAN).is_err());
+}
+
+#[test]
+fn invalid_frame_times_do_not_panic() {
+    let ctx = egui::Context::default();
+    let mut spinner = ModalSpinner::new();
+    let mut task = counting_task(u32::MAX)
+        .slice_duration(Duration::ZERO)
+        .target_fps(60.0);
+
+    spinner.open();
+
+    // The time jumping this far results in an infinite frame time.
+    for time in [0.0, 1e300, f64::MAX] {
+        let input = egui::RawInput {
+            time: Some(time),
+            ..Default::default()
+        };
+
+        let _ = ctx.run(input, |ctx| {
+            let _ = spinner.update_with_task(ctx, &mut task);
+        });
+    }
+
+    assert!(spinner.is_open());
+}
+
+#[test]
+fn maximum_slice_duration_does_not_overflow() {
+    let ctx = egui::Context::default();
+    let mut spinner = ModalSpinner::new();
+    let mut task = counting_task(3)
+        .slice_duration(Duration::MAX)
+        .target_fps(10.0);
+
+    spinner.open();
+
+    let _ = ctx.run(egui::RawInput::default(), |ctx| {
+        assert_eq!(spinner.update_with_task(ctx, &mut task), Some(3));
+    });
+}
@@ -0,0 +1,58 @@
+//! Tests that the cursor icon is not left changed by the modal, also if the content closure
+//! panics.
+
+use std::panic::{self, AssertUnwindSafe};
+
+use egui_modal_spinner::ModalSpinner;
+
+mod common;
+
+use common::Harness;
+
+/// Updates the spinner with content that changes the cursor icon and optionally panics.
+/// Returns the cursor icon of the frame, or None if the frame panicked.
+fn update(harness: &mut Harness, panic: bool) -> Option<egui::CursorIcon> {
+    let events = vec![egui::Event::PointerMoved(egui::pos2(400.0, 300.0))];
+
+    panic::catch_unwind(AssertUnwindSafe(|| {
+        harness.run_ui(events.clone(), |ctx, spinner| {
+            spinner.update_with_content(ctx, |ui| {
+                ui.ctx().set_cursor_icon(egui::CursorIcon::Wait);
+                assert!(!panic, "broken content");
+            });
+        });
+    }))
+    .ok()
+    .map(|()| harness.output.platform_output.cursor_icon)
+}
+
+#[test]
+fn cursor_icon_is_restored_after_close() {
+    let mut harness = Harness::closed(ModalSpinner::new().fade_in(false).fade_out(false));
+
+    harness.spinner.open();
+    assert_eq!(update(&mut harness, false), Some(egui::CursorIcon::Wait));
+
+    harness.spinner.close();
+    assert_eq!(update(&mut harness, false), Some(egui::CursorIcon::Default));
+}
+
+#[test]
+fn cursor_icon_is_restored_after_panic_in_content() {
+    let mut harness = Harness::closed(
+        ModalSpinner::new()
+            .fade_in(false)
+            .fade_out(false)
+            .catch_content_panics(false),
+    );
+
+    harness.spinner.open();
+    assert_eq!(update(&mut harness, true), None);
+
+    // The modal is still displayed in the next frame and restores the cursor once closed.
+    assert!(harness.spinner.is_open());
+    assert_eq!(update(&mut harness, false), Some(egui::CursorIcon::Wait));
+
+    harness.spinner.close();
+    assert_eq!(update(&mut harness, false), Some(egui::CursorIcon::Default));
+}
@@ -0,0 +1,50 @@
+//! Tests for replacing the default spinner with a custom widget.
+
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use egui_modal_spinner::{ModalSpinner, SpinnerWidget};
+
+/// The elapsed time, progress and available size passed to the widget.
+type Call = (Duration, Option<f32>, egui::Vec2);
+
+struct Recorder {
+    calls: Arc<Mutex<Vec<Call>>>,
+}
+
+impl SpinnerWidget for Recorder {
+    fn ui(&self, ui: &mut egui::Ui, elapsed: Duration, progress: Option<f32>) {
+        if let Ok(mut calls) = self.calls.lock() {
+            calls.push((elapsed, progress, ui.available_size()));
+        }
+    }
+}
+
+#[test]
+fn custom_spinner_replaces_default_spinner() {
+    let ctx = egui::Context::default();
+    let calls = Arc::new(Mutex::new(Vec::new()));
+    let mut spinner = ModalSpinner::new()
+        .fade_in(false)
+        .spinner_size(40.0)
+        .custom_spinner(Recorder {
+            calls: Arc::clone(&calls),
+        });
+
+    let _ = ctx.run(egui::RawInput::default(), |ctx| spinner.update(ctx));
+    assert!(calls.lock().is_ok_and(|calls| calls.is_empty()));
+
+    spinner.open();
+    spinner.set_progress(0.5);
+    thread::sleep(Duration::from_millis(10));
+    let _ = ctx.run(egui::RawInput::default(), |ctx| spinner.update(ctx));
+
+    let calls = calls.lock().map(|calls| calls.clone()).unwrap_or_default();
+    assert_eq!(calls.len(), 1);
+
+    let (elapsed, progress, size) = calls.first().copied().unwrap_or_default();
+    assert!(elapsed >= Duration::from_millis(10));
+    assert_eq!(progress, Some(0.5));
+    assert_eq!(size, egui::Vec2::splat(40.0));
+}
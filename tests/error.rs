@@ -0,0 +1,88 @@
+//! Tests for the error state displayed when the task failed.
+
+use std::thread;
+use std::time::Duration;
+
+use egui_modal_spinner::{ErrorAction, ModalSpinner, SpinnerState};
+
+mod common;
+
+use common::Harness;
+
+fn new_harness() -> Harness {
+    Harness::new(ModalSpinner::new().fade_in(false).fade_out(false))
+}
+
+#[test]
+fn failed_task_displays_error_until_dismissed() {
+    let mut harness = new_harness();
+
+    harness.spinner.fail("Connection lost");
+    harness.run(Vec::new());
+
+    assert_eq!(harness.spinner.state(), &SpinnerState::Error);
+    assert_eq!(harness.spinner.error(), Some("Connection lost"));
+    assert!(harness.text_rect("Connection lost").is_some());
+    assert!(harness.text_rect("Retry").is_some());
+
+    let pos = harness.text_rect("Dismiss").map(|rect| rect.center());
+    harness.click(pos.unwrap_or_default());
+
+    assert_eq!(
+        harness.spinner.take_error_action(),
+        Some(ErrorAction::Dismiss)
+    );
+    assert!(!harness.spinner.is_open());
+}
+
+#[test]
+fn retry_keeps_error_until_reopened() {
+    let mut harness = new_harness();
+
+    harness.spinner.fail("Connection lost");
+    harness.run(Vec::new());
+
+    let pos = harness.text_rect("Retry").map(|rect| rect.center());
+    harness.click(pos.unwrap_or_default());
+
+    assert_eq!(
+        harness.spinner.take_error_action(),
+        Some(ErrorAction::Retry)
+    );
+    assert_eq!(harness.spinner.take_error_action(), None);
+    assert_eq!(harness.spinner.state(), &SpinnerState::Error);
+
+    harness.spinner.open();
+    assert_eq!(harness.spinner.state(), &SpinnerState::Open);
+    assert_eq!(harness.spinner.error(), None);
+}
+
+#[test]
+fn timeout_does_not_close_failed_task() {
+    let ctx = egui::Context::default();
+    let mut spinner = ModalSpinner::new().timeout(Duration::from_millis(10));
+
+    spinner.open();
+    spinner.fail("Connection lost");
+
+    thread::sleep(Duration::from_millis(20));
+    let _ = ctx.run(egui::RawInput::default(), |ctx| spinner.update(ctx));
+
+    assert_eq!(spinner.state(), &SpinnerState::Error);
+    assert!(!spinner.timed_out());
+}
+
+#[test]
+fn describe_contains_error_and_failures() {
+    let mut spinner = ModalSpinner::new();
+
+    spinner.open();
+    spinner.set_item_count(3);
+    spinner.fail_item(1);
+    spinner.fail("Connection lost");
+
+    assert_eq!(
+        spinner.describe(),
+        "state: Error\nelapsed: 0\nerror: Connection lost\nfailures: 1"
+    );
+}
@@ -0,0 +1,79 @@
+//! Tests that reversing the fade animation continues from the current opacity.
+
+use egui_modal_spinner::ModalSpinner;
+
+mod common;
+
+use common::Harness;
+
+#[test]
+fn close_during_fade_in_fades_out_from_current_opacity() {
+    let mut harness = Harness::closed(ModalSpinner::new());
+
+    harness.spinner.open();
+    harness.run(Vec::new());
+    harness.run(Vec::new());
+    let fading_in = harness.modal_alpha().unwrap_or_default();
+    assert!(fading_in < 120);
+
+    harness.spinner.close();
+    harness.run(Vec::new());
+    let fading_out = harness.modal_alpha().unwrap_or_default();
+    assert!(fading_out < fading_in);
+}
+
+#[test]
+fn open_during_fade_out_fades_in_from_current_opacity() {
+    let mut harness = Harness::closed(ModalSpinner::new());
+
+    harness.spinner.open();
+    for _ in 0..10 {
+        harness.run(Vec::new());
+    }
+
+    harness.spinner.close();
+    harness.run(Vec::new());
+    let fading_out = harness.modal_alpha().unwrap_or_default();
+    assert!(fading_out > 0 && fading_out < 120);
+
+    harness.spinner.open();
+    harness.run(Vec::new());
+    let fading_in = harness.modal_alpha().unwrap_or_default();
+    assert!(fading_in > fading_out);
+    assert!(harness.spinner.should_disable_ui());
+}
+
+#[test]
+fn open_while_open_keeps_opacity() {
+    let mut harness = Harness::closed(ModalSpinner::new());
+
+    harness.spinner.open();
+    for _ in 0..10 {
+        harness.run(Vec::new());
+    }
+
+    harness.run(Vec::new());
+
+    let opened = harness.modal_alpha().unwrap_or_default();
+
+    harness.spinner.open();
+    harness.run(Vec::new());
+    let reopened = harness.modal_alpha().unwrap_or_default();
+    assert!(reopened >= opened);
+}
+
+#[test]
+fn close_while_closed_does_not_fade_out() {
+    let mut harness = Harness::closed(ModalSpinner::new().fade_out(false));
+
+    harness.spinner.open();
+    harness.run(Vec::new());
+    harness.spinner.close();
+    harness.run(Vec::new());
+
+    harness.spinner = harness.spinner.fade_out(true);
+    harness.spinner.close();
+    assert!(!harness.spinner.should_disable_ui());
+    harness.run(Vec::new());
+    assert_eq!(harness.modal_alpha(), None);
+}
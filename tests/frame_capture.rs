@@ -0,0 +1,144 @@
+//! Tests that the captured frame is displayed beneath the modal, by answering the
+//! screenshot request of the spinner like an integration would.
+#![cfg(feature = "frame-capture")]
+
+use std::sync::Arc;
+
+use egui_modal_spinner::ModalSpinner;
+
+mod common;
+
+use common::Harness;
+
+fn new_harness() -> Harness {
+    Harness::closed(ModalSpinner::new().freeze_background(true).fade_out(false))
+}
+
+/// Gets the user data of the screenshot requested by the spinner in the last frame.
+fn screenshot_request(harness: &Harness) -> Option<egui::UserData> {
+    harness
+        .output
+        .viewport_output
+        .get(&egui::ViewportId::ROOT)?
+        .commands
+        .iter()
+        .find_map(|command| match command {
+            egui::ViewportCommand::Screenshot(user_data) => Some(user_data.clone()),
+            _ => None,
+        })
+}
+
+/// Gets the pixels of the texture loaded by the spinner in the last frame.
+fn texture_pixels(harness: &Harness) -> Vec<egui::Color32> {
+    harness
+        .output
+        .textures_delta
+        .set
+        .iter()
+        .find_map(|(_, delta)| match &delta.image {
+            egui::ImageData::Color(image) => Some(image.pixels.clone()),
+            egui::ImageData::Font(_) => None,
+        })
+        .unwrap_or_default()
+}
+
+/// Runs a frame that replies to the given screenshot request.
+fn answer_screenshot(harness: &mut Harness, user_data: egui::UserData) {
+    let image = egui::ColorImage::new([8, 6], egui::Color32::from_rgb(200, 40, 10));
+
+    harness.run(vec![egui::Event::Screenshot {
+        viewport_id: egui::ViewportId::ROOT,
+        user_data,
+        image: Arc::new(image),
+    }]);
+}
+
+/// Opens the spinner and answers its screenshot request.
+fn open_and_capture(harness: &mut Harness) {
+    harness.spinner.open();
+    harness.run(Vec::new());
+
+    let request = screenshot_request(harness).unwrap_or_default();
+    answer_screenshot(harness, request);
+}
+
+#[test]
+fn screenshot_is_requested_when_opening() {
+    let mut harness = new_harness();
+
+    harness.spinner.open();
+    harness.run(Vec::new());
+
+    assert!(screenshot_request(&harness).is_some());
+    assert!(!harness.spinner.is_background_frozen());
+}
+
+#[test]
+fn background_is_frozen_after_capture() {
+    let mut harness = new_harness();
+
+    open_and_capture(&mut harness);
+
+    assert!(harness.spinner.is_background_frozen());
+}
+
+#[test]
+fn unrelated_screenshot_is_ignored() {
+    let mut harness = new_harness();
+
+    harness.spinner.open();
+    harness.run(Vec::new());
+    answer_screenshot(&mut harness, egui::UserData::new("unrelated"));
+
+    assert!(!harness.spinner.is_background_frozen());
+}
+
+#[test]
+fn background_is_no_longer_frozen_after_close() {
+    let mut harness = new_harness();
+
+    open_and_capture(&mut harness);
+    harness.spinner.close();
+    harness.run(Vec::new());
+
+    assert!(!harness.spinner.is_background_frozen());
+}
+
+#[test]
+fn capture_is_discarded_on_resize() {
+    let mut harness = new_harness();
+
+    open_and_capture(&mut harness);
+    harness.screen_size = egui::vec2(640.0, 480.0);
+    harness.run(Vec::new());
+
+    assert!(!harness.spinner.is_background_frozen());
+}
+
+#[test]
+fn no_screenshot_is_requested_by_default() {
+    let mut harness = new_harness();
+    harness.spinner = ModalSpinner::new();
+
+    harness.spinner.open();
+    harness.run(Vec::new());
+
+    assert!(screenshot_request(&harness).is_none());
+}
+
+#[test]
+fn frozen_background_is_desaturated() {
+    let mut harness = new_harness();
+    harness.spinner = ModalSpinner::new()
+        .freeze_background(true)
+        .desaturate_background(true);
+
+    open_and_capture(&mut harness);
+
+    assert!(harness.spinner.is_background_frozen());
+    let pixels = texture_pixels(&harness);
+    assert_eq!(pixels.len(), 8 * 6);
+    assert!(pixels
+        .iter()
+        .all(|pixel| pixel.r() == pixel.g() && pixel.g() == pixel.b() && pixel.a() == 255));
+}
@@ -0,0 +1,75 @@
+//! Tests for the graph of dependent tasks displayed beneath the spinner.
+
+use egui_modal_spinner::{ModalSpinner, StepState, TaskGraph};
+
+#[test]
+fn tasks_are_ready_once_dependencies_are_completed() {
+    let mut graph = TaskGraph::new();
+    let a = graph.add_task("A", []);
+    let b = graph.add_task("B", [a]);
+    let c = graph.add_task("C", [a, b]);
+
+    assert_eq!(graph.ready(), vec![a]);
+    assert!(!graph.start(b));
+    assert!(!graph.complete(a));
+
+    assert!(graph.start(a));
+    assert_eq!(graph.active(), vec![a]);
+    assert!(graph.ready().is_empty());
+
+    assert!(graph.complete(a));
+    assert_eq!(graph.ready(), vec![b]);
+    assert!((graph.progress() - 1.0 / 3.0).abs() < f32::EPSILON);
+
+    graph.start(b);
+    graph.complete(b);
+    graph.start(c);
+    assert_eq!(graph.state(c), Some(StepState::Active));
+    assert!(!graph.is_finished());
+
+    graph.complete(c);
+    assert!(graph.is_finished());
+}
+
+#[test]
+fn spinner_displays_active_tasks_and_progress() {
+    let ctx = egui::Context::default();
+    let mut spinner = ModalSpinner::new().fade_in(false);
+
+    let mut graph = TaskGraph::new();
+    let download = graph.add_task("Downloading", []);
+    let fonts = graph.add_task("Loading fonts", []);
+    let _install = graph.add_task("Installing", [download, fonts]);
+    graph.start(download);
+    graph.start(fonts);
+
+    spinner.set_steps(["Step"]);
+    spinner.set_task_graph(graph);
+    spinner.open();
+
+    if let Some(graph) = spinner.task_graph_mut() {
+        graph.complete(fonts);
+    }
+
+    // The fonts are only available after the first frame.
+    let mut texts = Vec::new();
+    for _ in 0..2 {
+        let output = ctx.run(egui::RawInput::default(), |ctx| spinner.update(ctx));
+
+        texts = output
+            .shapes
+            .iter()
+            .filter_map(|shape| match &shape.shape {
+                egui::Shape::Text(text) => Some(text.galley.text().to_owned()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+    }
+
+    assert!(texts.iter().any(|text| text == "Downloading"));
+    assert!(!texts.iter().any(|text| text == "Loading fonts"));
+    assert!(!texts.iter().any(|text| text == "Step"));
+    assert!(spinner
+        .progress()
+        .is_some_and(|progress| (progress - 1.0 / 3.0).abs() < f32::EPSILON));
+}
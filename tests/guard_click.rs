@@ -0,0 +1,112 @@
+//! Tests that clicks are only accepted while no spinner is active.
+
+use egui_modal_spinner::ModalSpinner;
+
+mod common;
+
+use common::Harness;
+
+const BUTTON_POS: egui::Pos2 = egui::pos2(20.0, 15.0);
+
+/// Runs a frame with a button, the spinner of the harness and the other spinners, and
+/// returns the results of `ModalSpinner::guard_click` for the spinner of the harness and
+/// `ModalSpinner::guard_click_any`.
+fn run(
+    harness: &mut Harness,
+    others: &mut [&mut ModalSpinner],
+    events: Vec<egui::Event>,
+) -> (bool, bool) {
+    let mut guarded = (false, false);
+
+    harness.run_ui(events, |ctx, spinner| {
+        egui::CentralPanel::default().show(ctx, |ui| {
+            let re = ui.button("Run");
+            guarded = (spinner.guard_click(&re), ModalSpinner::guard_click_any(&re));
+        });
+
+        spinner.update(ctx);
+
+        for other in others.iter_mut() {
+            other.update(ctx);
+        }
+    });
+
+    guarded
+}
+
+/// Clicks the button, with the release happening in the last frame.
+fn click(harness: &mut Harness, others: &mut [&mut ModalSpinner]) -> (bool, bool) {
+    let mut guarded = (false, false);
+
+    for events in common::click_events(BUTTON_POS, egui::PointerButton::Primary) {
+        guarded = run(harness, others, events);
+    }
+
+    guarded
+}
+
+/// Clicks the button, with all events being delivered within a single frame.
+fn queued_click(harness: &mut Harness, others: &mut [&mut ModalSpinner]) -> (bool, bool) {
+    let events = common::click_events(BUTTON_POS, egui::PointerButton::Primary).concat();
+    run(harness, others, events)
+}
+
+#[test]
+fn clicks_are_accepted_without_spinner() {
+    let mut harness = Harness::closed(ModalSpinner::new());
+
+    assert_eq!(click(&mut harness, &mut []), (true, true));
+}
+
+#[test]
+fn clicks_are_rejected_right_after_close() {
+    let mut harness = Harness::closed(ModalSpinner::new().fade_out(false));
+
+    harness.spinner.open();
+    let _ = run(&mut harness, &mut [], Vec::new());
+    harness.spinner.close();
+
+    // Clicks that were queued while the spinner was open.
+    assert_eq!(queued_click(&mut harness, &mut []), (false, false));
+    assert_eq!(queued_click(&mut harness, &mut []), (false, false));
+    assert_eq!(queued_click(&mut harness, &mut []), (true, true));
+}
+
+#[test]
+fn clicks_are_rejected_globally_right_after_other_spinner_closed() {
+    let mut harness = Harness::closed(ModalSpinner::new());
+    let mut other = ModalSpinner::new().id("other").fade_out(false);
+
+    other.open();
+    let _ = run(&mut harness, &mut [&mut other], Vec::new());
+    other.close();
+
+    let _ = queued_click(&mut harness, &mut [&mut other]);
+    let guarded = queued_click(&mut harness, &mut [&mut other]);
+    assert_eq!(guarded, (true, false));
+}
+
+#[test]
+fn clicks_are_accepted_when_closing_closed_spinner() {
+    let mut harness = Harness::closed(ModalSpinner::new());
+
+    // A spinner that was never opened, closed every frame.
+    for _ in 0..3 {
+        harness.spinner.close();
+        let _ = run(&mut harness, &mut [], Vec::new());
+    }
+
+    assert_eq!(click(&mut harness, &mut []), (true, true));
+
+    // A spinner that is closed every frame after its session finished.
+    harness.spinner.open();
+    let _ = run(&mut harness, &mut [], Vec::new());
+
+    for _ in 0..30 {
+        harness.spinner.close();
+        let _ = run(&mut harness, &mut [], Vec::new());
+    }
+
+    harness.spinner.close();
+    assert_eq!(click(&mut harness, &mut []), (true, true));
+}
@@ -0,0 +1,249 @@
+//! Tests for controlling the spinner from worker threads using handles.
+
+use std::thread;
+
+use egui_modal_spinner::{ModalSpinner, SpinnerGuard, SpinnerHandle, SpinnerProgress};
+
+mod common;
+
+use common::Harness;
+
+const fn assert_send_sync<T: Send + Sync + Clone>() {}
+
+#[test]
+const fn handle_is_send_sync_and_clone() {
+    assert_send_sync::<SpinnerHandle>();
+    assert_send_sync::<SpinnerProgress>();
+}
+
+#[test]
+fn handle_closes_spinner_from_thread() {
+    let mut harness = Harness::closed(ModalSpinner::new());
+    let handle = harness.spinner.handle();
+
+    harness.spinner.open();
+    harness.run(Vec::new());
+
+    let _ = thread::spawn(move || handle.close()).join();
+    assert!(harness.spinner.is_open());
+
+    harness.run(Vec::new());
+    assert!(!harness.spinner.is_open());
+}
+
+#[test]
+fn handle_sets_progress_from_thread() {
+    let mut harness = Harness::closed(ModalSpinner::new());
+    let handle = harness.spinner.handle();
+
+    harness.spinner.open();
+    harness.run(Vec::new());
+
+    let _ = thread::spawn(move || {
+        handle.set_progress(0.25);
+        handle.set_progress(2.0);
+    })
+    .join();
+
+    harness.run(Vec::new());
+    assert_eq!(harness.spinner.progress(), Some(1.0));
+}
+
+#[test]
+fn requests_while_closed_are_discarded() {
+    let mut harness = Harness::closed(ModalSpinner::new());
+    let handle = harness.spinner.handle();
+
+    handle.close();
+    harness.run(Vec::new());
+
+    harness.spinner.open();
+    harness.run(Vec::new());
+
+    assert!(harness.spinner.is_open());
+}
+
+#[test]
+fn progress_source_is_read_every_frame() {
+    let progress = SpinnerProgress::new();
+    let mut harness = Harness::closed(ModalSpinner::new().progress_source(progress.clone()));
+
+    harness.spinner.open();
+    harness.run(Vec::new());
+    assert_eq!(harness.spinner.progress(), None);
+
+    let worker = progress.clone();
+    let _ = thread::spawn(move || worker.set(1.5)).join();
+    assert_eq!(progress.get(), Some(1.0));
+
+    harness.run(Vec::new());
+    assert_eq!(harness.spinner.progress(), Some(1.0));
+}
+
+#[test]
+fn progress_source_is_cleared_when_opening() {
+    let progress = SpinnerProgress::new();
+    let mut spinner = ModalSpinner::new().progress_source(progress.clone());
+
+    progress.set(0.5);
+    progress.set(f32::NAN);
+    assert_eq!(progress.get(), Some(0.5));
+
+    spinner.open();
+    assert_eq!(progress.get(), None);
+}
+
+#[test]
+fn handle_sets_message_from_thread() {
+    let mut harness = Harness::closed(ModalSpinner::new());
+    let handle = harness.spinner.handle();
+
+    harness.spinner.open();
+    harness.run(Vec::new());
+
+    let worker = handle.clone();
+    let _ = thread::spawn(move || worker.set_message("Loading cats...")).join();
+    assert_eq!(harness.spinner.message(), None);
+
+    harness.run(Vec::new());
+    assert_eq!(harness.spinner.message(), Some("Loading cats..."));
+
+    handle.clear_message();
+    harness.run(Vec::new());
+    assert_eq!(harness.spinner.message(), None);
+}
+
+#[test]
+fn message_is_rendered_beneath_spinner() {
+    let mut harness = Harness::new(ModalSpinner::new().fade_in(false).show_elapsed_time(false));
+
+    harness.spinner.set_message("Loading dogs...");
+    harness.run(Vec::new());
+
+    assert!(harness.text_rect("Loading dogs...").is_some());
+}
+
+#[test]
+fn repaint_callback_is_called_from_thread() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    let requests = Arc::new(AtomicUsize::new(0));
+    let requests_clone = Arc::clone(&requests);
+
+    let spinner = ModalSpinner::new().on_repaint_request(move || {
+        requests_clone.fetch_add(1, Ordering::Relaxed);
+    });
+    let handle = spinner.handle();
+
+    let _ = thread::spawn(move || {
+        handle.set_progress(0.5);
+        handle.close();
+    })
+    .join();
+
+    assert_eq!(requests.load(Ordering::Relaxed), 2);
+}
+
+#[test]
+fn guard_closes_spinner_on_early_return() {
+    let mut harness = Harness::closed(ModalSpinner::new());
+
+    let task = |guard: SpinnerGuard| -> Result<(), ()> {
+        let _guard = guard;
+        Err(())?;
+        Ok(())
+    };
+
+    let guard = harness.spinner.open_guard();
+    harness.run(Vec::new());
+
+    let _ = thread::spawn(move || task(guard)).join();
+    assert!(harness.spinner.is_open());
+
+    harness.run(Vec::new());
+    assert!(!harness.spinner.is_open());
+}
+
+#[test]
+fn stale_guard_does_not_close_new_opening() {
+    let mut harness = Harness::closed(ModalSpinner::new());
+
+    let guard = harness.spinner.open_guard();
+    harness.spinner.close();
+    harness.run(Vec::new());
+
+    harness.spinner.open();
+    drop(guard);
+    harness.run(Vec::new());
+
+    assert!(harness.spinner.is_open());
+}
+
+#[test]
+fn stats_count_sent_dropped_and_applied_updates() {
+    let mut harness = Harness::closed(ModalSpinner::new());
+    let handle = harness.spinner.handle();
+
+    // Updates sent while the spinner is closed are discarded.
+    handle.set_progress(0.1);
+    harness.run(Vec::new());
+
+    harness.spinner.open();
+    handle.set_progress(0.2);
+    handle.set_progress(0.3);
+    handle.set_message("Loading cats...");
+    handle.set_message("Loading dogs...");
+    handle.clear_message();
+    harness.run(Vec::new());
+
+    let stats = handle.stats();
+    assert_eq!(stats.sent, 6);
+    assert_eq!(stats.dropped, 4);
+    assert_eq!(stats.applied, 2);
+    assert_eq!(harness.spinner.progress(), Some(0.3));
+    assert_eq!(harness.spinner.message(), None);
+}
+
+#[test]
+fn cloned_spinner_has_own_handle() {
+    let mut harness = Harness::closed(ModalSpinner::new().fade_out(false));
+
+    harness.spinner.open();
+    harness.run(Vec::new());
+
+    let mut clone = Harness::closed(harness.spinner.clone());
+    clone.spinner.handle().close();
+    clone.spinner.handle().set_progress(0.5);
+
+    harness.run(Vec::new());
+    assert!(harness.spinner.is_open());
+    assert_eq!(harness.spinner.progress(), None);
+
+    clone.run(Vec::new());
+    assert!(!clone.spinner.is_open());
+    assert_eq!(clone.spinner.progress(), Some(0.5));
+}
+
+#[test]
+fn only_latest_item_updates_are_kept() {
+    let mut harness = Harness::closed(ModalSpinner::new());
+    let handle = harness.spinner.handle();
+
+    harness.spinner.open();
+    harness.spinner.set_item_count(2);
+
+    for _ in 0..1000 {
+        handle.fail_item(0);
+        handle.complete_item(1);
+    }
+
+    handle.complete_item(0);
+    harness.run(Vec::new());
+
+    let stats = handle.stats();
+    assert_eq!(stats.sent, 2001);
+    assert_eq!(stats.dropped, 1999);
+    assert_eq!(stats.applied, 2);
+    assert!(harness.spinner.failures().is_empty());
+}
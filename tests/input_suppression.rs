@@ -0,0 +1,428 @@
+//! Simulates user input while the modal spinner is open and asserts that none of it reaches
+//! the widgets rendered beneath the modal.
+
+use egui_modal_spinner::ModalSpinner;
+
+mod common;
+
+use common::pointer_button;
+
+/// Small headless test harness that runs frames of a single button and text edit
+/// with a spinner rendered above them.
+struct Harness {
+    ctx: egui::Context,
+    spinner: ModalSpinner,
+    text: String,
+    clicks: usize,
+    context_menu_open: bool,
+    button_rect: egui::Rect,
+    drag_rect: egui::Rect,
+    text_edit_id: egui::Id,
+    /// If the widgets should be drawn inside a foreground window after the spinner was updated,
+    /// instead of inside a central panel before the spinner is updated.
+    draw_window_after_spinner: bool,
+}
+
+impl Harness {
+    fn new() -> Self {
+        Self::with_window(false)
+    }
+
+    fn with_window(draw_window_after_spinner: bool) -> Self {
+        let mut harness = Self {
+            ctx: egui::Context::default(),
+            spinner: ModalSpinner::new(),
+            text: String::new(),
+            clicks: 0,
+            context_menu_open: false,
+            button_rect: egui::Rect::NOTHING,
+            drag_rect: egui::Rect::NOTHING,
+            text_edit_id: egui::Id::new("text_edit"),
+            draw_window_after_spinner,
+        };
+
+        // Run a first frame so the widget positions are known.
+        harness.run(Vec::new());
+
+        harness
+    }
+
+    fn run(&mut self, events: Vec<egui::Event>) {
+        let ctx = self.ctx.clone();
+
+        let _ = ctx.run(common::screen_input(events), |ctx| {
+            if self.draw_window_after_spinner {
+                self.spinner.update(ctx);
+                egui::Window::new("Window")
+                    .order(egui::Order::Foreground)
+                    .show(ctx, |ui| self.ui_widgets(ui));
+            } else {
+                egui::CentralPanel::default().show(ctx, |ui| self.ui_widgets(ui));
+                self.spinner.update(ctx);
+            }
+        });
+    }
+
+    fn ui_widgets(&mut self, ui: &mut egui::Ui) {
+        let re = ui.button("Click me");
+
+        if re.clicked() {
+            self.clicks += 1;
+        }
+
+        re.context_menu(|ui| {
+            ui.label("Context menu");
+        });
+
+        self.context_menu_open = re.context_menu_opened();
+        self.button_rect = re.rect;
+
+        ui.add(egui::TextEdit::singleline(&mut self.text).id(self.text_edit_id));
+
+        let re = ui.add(egui::Label::new("Drag me").sense(egui::Sense::drag()));
+
+        if re.drag_started() {
+            egui::DragAndDrop::set_payload(ui.ctx(), "payload");
+        }
+
+        self.drag_rect = re.rect;
+    }
+
+    fn click(&mut self, pos: egui::Pos2, button: egui::PointerButton) {
+        for events in common::click_events(pos, button) {
+            self.run(events);
+        }
+    }
+
+    fn click_button(&mut self) {
+        self.click(self.button_rect.center(), egui::PointerButton::Primary);
+    }
+
+    fn secondary_click_button(&mut self) {
+        self.click(self.button_rect.center(), egui::PointerButton::Secondary);
+    }
+
+    fn start_drag(&mut self) {
+        let pos = self.drag_rect.center();
+        let moved = pos + egui::vec2(20.0, 0.0);
+
+        self.run(vec![egui::Event::PointerMoved(pos)]);
+        self.run(vec![pointer_button(
+            pos,
+            egui::PointerButton::Primary,
+            true,
+        )]);
+        self.run(vec![egui::Event::PointerMoved(moved)]);
+    }
+
+    fn focus_text_edit(&mut self) {
+        self.ctx.memory_mut(|m| m.request_focus(self.text_edit_id));
+        self.run(Vec::new());
+    }
+
+    fn type_text(&mut self, text: &str) {
+        self.run(vec![egui::Event::Text(text.to_owned())]);
+    }
+}
+
+#[test]
+fn click_reaches_widget_when_closed() {
+    let mut harness = Harness::new();
+
+    harness.click_button();
+
+    assert_eq!(harness.clicks, 1);
+}
+
+#[test]
+fn click_is_suppressed_when_open() {
+    let mut harness = Harness::new();
+
+    harness.spinner.open();
+    harness.run(Vec::new());
+    harness.click_button();
+
+    assert_eq!(harness.clicks, 0);
+}
+
+#[test]
+fn click_is_suppressed_on_the_frame_the_spinner_opens() {
+    let mut harness = Harness::new();
+
+    harness.spinner.open();
+    harness.click_button();
+
+    assert_eq!(harness.clicks, 0);
+}
+
+#[test]
+fn click_is_suppressed_without_fade_in() {
+    let mut harness = Harness::new();
+    harness.spinner = ModalSpinner::new().fade_in(false);
+
+    harness.spinner.open();
+    harness.click_button();
+
+    assert_eq!(harness.clicks, 0);
+}
+
+#[test]
+fn click_is_suppressed_while_fading_out() {
+    let mut harness = Harness::new();
+
+    harness.spinner.open();
+
+    // Let the modal fade in completely, so it fades out for multiple frames.
+    for _ in 0..10 {
+        harness.run(Vec::new());
+    }
+
+    harness.spinner.close();
+    harness.run(Vec::new());
+    harness.click_button();
+
+    assert_eq!(harness.clicks, 0);
+}
+
+#[test]
+fn click_reaches_widget_after_close() {
+    let mut harness = Harness::new();
+    harness.spinner = ModalSpinner::new().fade_out(false);
+
+    harness.spinner.open();
+    harness.run(Vec::new());
+    harness.spinner.close();
+    harness.run(Vec::new());
+    harness.click_button();
+
+    assert_eq!(harness.clicks, 1);
+}
+
+#[test]
+fn text_input_is_suppressed_when_open() {
+    let mut harness = Harness::new();
+
+    harness.focus_text_edit();
+    harness.type_text("a");
+    assert_eq!(harness.text, "a");
+
+    harness.spinner.open();
+    harness.run(Vec::new());
+    harness.type_text("b");
+
+    assert_eq!(harness.text, "a");
+}
+
+#[test]
+fn key_presses_are_suppressed_when_open() {
+    let mut harness = Harness::new();
+
+    harness.focus_text_edit();
+    harness.type_text("abc");
+
+    harness.spinner.open();
+    harness.run(Vec::new());
+    harness.run(vec![egui::Event::Key {
+        key: egui::Key::Backspace,
+        physical_key: None,
+        pressed: true,
+        repeat: false,
+        modifiers: egui::Modifiers::NONE,
+    }]);
+
+    assert_eq!(harness.text, "abc");
+}
+
+#[test]
+fn click_is_suppressed_for_window_drawn_after_spinner() {
+    let mut harness = Harness::with_window(true);
+
+    harness.click_button();
+    assert_eq!(harness.clicks, 1);
+
+    harness.spinner.open();
+    harness.run(Vec::new());
+    harness.click_button();
+
+    assert_eq!(harness.clicks, 1);
+}
+
+#[test]
+fn context_menu_state_is_removed_when_opening() {
+    let mut harness = Harness::new();
+
+    harness.secondary_click_button();
+    harness.run(Vec::new());
+    assert!(harness.ctx.is_context_menu_open());
+
+    // The spinner removes the state egui stores the context menu under. This fails if egui
+    // changes the ID of that state.
+    harness.spinner.open();
+    harness.run(Vec::new());
+    assert!(!harness.ctx.is_context_menu_open());
+}
+
+#[test]
+fn context_menu_is_closed_when_opening() {
+    let mut harness = Harness::new();
+    harness.spinner = ModalSpinner::new().fade_out(false);
+
+    harness.secondary_click_button();
+    harness.run(Vec::new());
+    assert!(harness.context_menu_open);
+
+    harness.spinner.open();
+    harness.run(Vec::new());
+    harness.run(Vec::new());
+    assert!(!harness.context_menu_open);
+
+    harness.spinner.close();
+    harness.run(Vec::new());
+    assert!(!harness.context_menu_open);
+}
+
+#[test]
+fn context_menu_is_suppressed_when_open() {
+    let mut harness = Harness::new();
+
+    harness.spinner.open();
+    harness.run(Vec::new());
+    harness.secondary_click_button();
+    harness.run(Vec::new());
+
+    assert!(!harness.context_menu_open);
+}
+
+#[test]
+fn popup_is_closed_when_opening() {
+    let mut harness = Harness::new();
+
+    harness
+        .ctx
+        .memory_mut(|m| m.open_popup(egui::Id::new("popup")));
+
+    harness.spinner.open();
+    harness.run(Vec::new());
+
+    assert!(!harness.ctx.memory(egui::Memory::any_popup_open));
+}
+
+#[test]
+fn pointer_position_is_kept_when_opening() {
+    let mut harness = Harness::new();
+    let pos = harness.drag_rect.center();
+
+    harness.run(vec![egui::Event::PointerMoved(pos)]);
+    harness.run(vec![pointer_button(
+        pos,
+        egui::PointerButton::Primary,
+        true,
+    )]);
+
+    harness.spinner.open();
+    harness.run(Vec::new());
+
+    let (latest_pos, any_down) = harness
+        .ctx
+        .input(|i| (i.pointer.latest_pos(), i.pointer.any_down()));
+    assert_eq!(latest_pos, Some(pos));
+    assert!(!any_down);
+}
+
+#[test]
+fn drag_is_cancelled_when_opening() {
+    let mut harness = Harness::new();
+
+    harness.start_drag();
+    assert!(harness.ctx.dragged_id().is_some());
+    assert!(egui::DragAndDrop::has_any_payload(&harness.ctx));
+
+    harness.spinner.open();
+    harness.run(Vec::new());
+
+    assert!(harness.ctx.dragged_id().is_none());
+    assert!(!egui::DragAndDrop::has_any_payload(&harness.ctx));
+}
+
+#[test]
+fn drag_does_not_resume_after_close() {
+    let mut harness = Harness::new();
+    harness.spinner = ModalSpinner::new().fade_out(false);
+
+    harness.start_drag();
+
+    harness.spinner.open();
+    harness.run(Vec::new());
+    harness.spinner.close();
+    harness.run(Vec::new());
+
+    // The pointer button is still held down while moving
+    let pos = harness.drag_rect.center() + egui::vec2(40.0, 0.0);
+    harness.run(vec![egui::Event::PointerMoved(pos)]);
+
+    assert!(harness.ctx.dragged_id().is_none());
+}
+
+#[test]
+fn press_before_open_does_not_click_after_close() {
+    let mut harness = Harness::new();
+    harness.spinner = ModalSpinner::new().fade_out(false);
+
+    let pos = harness.button_rect.center();
+    harness.run(vec![egui::Event::PointerMoved(pos)]);
+    harness.run(vec![pointer_button(
+        pos,
+        egui::PointerButton::Primary,
+        true,
+    )]);
+
+    harness.spinner.open();
+    harness.run(Vec::new());
+    harness.spinner.close();
+    harness.run(Vec::new());
+
+    harness.run(vec![pointer_button(
+        pos,
+        egui::PointerButton::Primary,
+        false,
+    )]);
+
+    assert_eq!(harness.clicks, 0);
+}
+
+#[test]
+fn focus_is_restored_after_close() {
+    let mut harness = Harness::new();
+    harness.spinner = ModalSpinner::new().fade_out(false);
+
+    harness.focus_text_edit();
+
+    harness.spinner.open();
+    harness.run(Vec::new());
+    harness.run(Vec::new());
+    assert!(!harness.ctx.memory(|m| m.has_focus(harness.text_edit_id)));
+
+    harness.spinner.close();
+    harness.run(Vec::new());
+    harness.type_text("a");
+
+    assert!(harness.ctx.memory(|m| m.has_focus(harness.text_edit_id)));
+    assert_eq!(harness.text, "a");
+}
+
+#[test]
+fn focus_is_restored_after_fade_out() {
+    let mut harness = Harness::new();
+
+    harness.focus_text_edit();
+
+    harness.spinner.open();
+    harness.run(Vec::new());
+    harness.spinner.close();
+
+    for _ in 0..10 {
+        harness.run(Vec::new());
+    }
+
+    assert!(harness.ctx.memory(|m| m.has_focus(harness.text_edit_id)));
+}
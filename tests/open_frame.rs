@@ -0,0 +1,91 @@
+//! Tests that the modal is painted on the same frame the spinner is opened.
+
+use egui_modal_spinner::ModalSpinner;
+
+mod common;
+
+use common::{Harness, SCREEN_SIZE};
+
+/// Runs a frame that opens and updates the spinner.
+fn open_and_update(harness: &mut Harness) {
+    harness.run_ui(Vec::new(), |ctx, spinner| spinner.open_and_update(ctx));
+}
+
+#[test]
+fn modal_is_painted_on_open_frame_without_fade_in() {
+    let mut harness = Harness::closed(ModalSpinner::new().fade_in(false));
+
+    open_and_update(&mut harness);
+
+    assert!(harness.spinner.is_open());
+    assert_eq!(harness.modal_alpha(), Some(120));
+}
+
+#[test]
+fn modal_starts_fading_in_on_open_frame() {
+    let mut harness = Harness::closed(ModalSpinner::new());
+
+    open_and_update(&mut harness);
+
+    assert!(harness
+        .modal_alpha()
+        .is_some_and(|alpha| alpha > 0 && alpha < 120));
+}
+
+#[test]
+fn modal_is_painted_when_reopened() {
+    let mut harness = Harness::closed(ModalSpinner::new().fade_in(false).fade_out(false));
+
+    open_and_update(&mut harness);
+    harness.spinner.close();
+    harness.run(Vec::new());
+    assert_eq!(harness.modal_alpha(), None);
+
+    open_and_update(&mut harness);
+    assert_eq!(harness.modal_alpha(), Some(120));
+}
+
+#[test]
+fn modal_covers_screen_rect_with_offset_origin() {
+    let ctx = egui::Context::default();
+    let mut spinner = ModalSpinner::new().fade_in(false);
+    let screen_rect = egui::Rect::from_min_size(egui::pos2(100.0, 50.0), egui::vec2(400.0, 300.0));
+
+    let input = egui::RawInput {
+        screen_rect: Some(screen_rect),
+        ..Default::default()
+    };
+
+    let output = ctx.run(input, |ctx| spinner.open_and_update(ctx));
+
+    let backdrop = output.shapes.iter().find_map(|shape| match &shape.shape {
+        egui::Shape::Rect(rect) if rect.fill.a() == 120 => Some(rect.rect),
+        _ => None,
+    });
+    let spinner_center = output.shapes.iter().find_map(|shape| match &shape.shape {
+        egui::Shape::Path(path) => Some(path.visual_bounding_rect().center()),
+        _ => None,
+    });
+
+    assert_eq!(backdrop, Some(screen_rect));
+    assert!(spinner_center.is_some_and(|center| screen_rect.contains(center)));
+}
+
+#[test]
+fn painting_is_skipped_while_screen_rect_is_empty() {
+    let mut harness = Harness::closed(ModalSpinner::new().fade_in(false));
+    harness.spinner.open();
+
+    harness.screen_size = egui::Vec2::ZERO;
+    harness.run(Vec::new());
+
+    assert!(harness.spinner.rendering_skipped());
+    assert!(harness.spinner.is_open());
+    assert!(harness.output.shapes.is_empty());
+
+    harness.screen_size = SCREEN_SIZE;
+    harness.run(Vec::new());
+
+    assert!(!harness.spinner.rendering_skipped());
+    assert_eq!(harness.modal_alpha(), Some(120));
+}
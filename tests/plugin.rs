@@ -0,0 +1,194 @@
+//! Tests that the plugin renders open spinners the application stopped updating.
+
+use egui_modal_spinner::{ModalSpinner, ModalSpinnerPlugin};
+
+mod common;
+
+/// Headless harness running frames of a single button, with a spinner that
+/// is only updated while `update_spinner` is true.
+struct Harness {
+    ctx: egui::Context,
+    spinner: Option<ModalSpinner>,
+    update_spinner: bool,
+    clicks: usize,
+    button_rect: egui::Rect,
+}
+
+impl Harness {
+    fn new() -> Self {
+        let ctx = egui::Context::default();
+        ModalSpinnerPlugin::install(&ctx);
+
+        let mut harness = Self {
+            ctx,
+            spinner: Some(ModalSpinner::new().fade_out(false)),
+            update_spinner: true,
+            clicks: 0,
+            button_rect: egui::Rect::NOTHING,
+        };
+
+        // Run a first frame so the button position is known.
+        harness.run(Vec::new());
+
+        harness
+    }
+
+    fn spinner(&mut self) -> &mut ModalSpinner {
+        self.spinner.get_or_insert_with(ModalSpinner::new)
+    }
+
+    fn run(&mut self, events: Vec<egui::Event>) {
+        let ctx = self.ctx.clone();
+
+        let _ = ctx.run(common::screen_input(events), |ctx| {
+            egui::CentralPanel::default().show(ctx, |ui| {
+                let re = ui.button("Click me");
+
+                if re.clicked() {
+                    self.clicks += 1;
+                }
+
+                self.button_rect = re.rect;
+            });
+
+            if self.update_spinner {
+                if let Some(spinner) = &mut self.spinner {
+                    spinner.update(ctx);
+                }
+            }
+        });
+    }
+
+    fn click_button(&mut self) {
+        let pos = self.button_rect.center();
+
+        for events in common::click_events(pos, egui::PointerButton::Primary) {
+            self.run(events);
+        }
+    }
+}
+
+#[test]
+fn install_is_idempotent() {
+    let ctx = egui::Context::default();
+    assert!(!ModalSpinnerPlugin::is_installed(&ctx));
+
+    ModalSpinnerPlugin::install(&ctx);
+    ModalSpinnerPlugin::install(&ctx);
+
+    assert!(ModalSpinnerPlugin::is_installed(&ctx));
+}
+
+#[test]
+fn open_spinner_is_rendered_when_not_updated() {
+    let mut harness = Harness::new();
+
+    harness.spinner().open();
+    harness.run(Vec::new());
+
+    harness.update_spinner = false;
+    harness.click_button();
+
+    assert_eq!(harness.clicks, 0);
+}
+
+#[test]
+fn closed_spinner_is_no_longer_rendered() {
+    let mut harness = Harness::new();
+
+    harness.spinner().open();
+    harness.run(Vec::new());
+
+    harness.update_spinner = false;
+    harness.spinner().close();
+    harness.run(Vec::new());
+    harness.click_button();
+
+    assert_eq!(harness.clicks, 1);
+}
+
+#[test]
+fn dropped_spinner_is_no_longer_rendered() {
+    let mut harness = Harness::new();
+
+    harness.spinner().open();
+    harness.run(Vec::new());
+
+    harness.update_spinner = false;
+    harness.spinner = None;
+    harness.run(Vec::new());
+    harness.click_button();
+
+    assert_eq!(harness.clicks, 1);
+}
+
+#[test]
+fn spinner_is_not_rendered_without_plugin() {
+    let mut harness = Harness::new();
+    harness.ctx = egui::Context::default();
+    harness.run(Vec::new());
+
+    harness.spinner().open();
+    harness.run(Vec::new());
+
+    harness.update_spinner = false;
+    harness.click_button();
+
+    assert_eq!(harness.clicks, 1);
+}
+
+#[test]
+fn handle_requests_are_kept_for_skipped_spinner() {
+    let mut harness = Harness::new();
+    let handle = harness.spinner().handle();
+
+    harness.spinner().open();
+    harness.run(Vec::new());
+
+    harness.update_spinner = false;
+    handle.set_progress(0.5);
+    harness.run(Vec::new());
+    handle.close();
+    harness.run(Vec::new());
+
+    // A spinner closed by a handle is no longer rendered by the plugin.
+    harness.click_button();
+    assert_eq!(harness.clicks, 1);
+
+    harness.update_spinner = true;
+    harness.run(Vec::new());
+
+    assert!(!harness.spinner().is_open());
+    assert_eq!(harness.spinner().progress(), Some(0.5));
+}
+
+#[test]
+fn plugin_does_not_call_lifecycle_callbacks() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    let mut harness = Harness::new();
+    let closed = Arc::new(AtomicUsize::new(0));
+
+    harness.spinner = Some(ModalSpinner::new().fade_out(false).on_close({
+        let closed = Arc::clone(&closed);
+        move || {
+            closed.fetch_add(1, Ordering::Relaxed);
+        }
+    }));
+
+    harness.spinner().open();
+    harness.run(Vec::new());
+
+    harness.update_spinner = false;
+    harness.spinner().handle().close();
+    harness.run(Vec::new());
+    harness.run(Vec::new());
+    assert_eq!(closed.load(Ordering::Relaxed), 0);
+    assert!(harness.spinner().is_open());
+
+    harness.update_spinner = true;
+    harness.run(Vec::new());
+    harness.run(Vec::new());
+    assert_eq!(closed.load(Ordering::Relaxed), 1);
+}
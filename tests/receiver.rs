@@ -0,0 +1,93 @@
+//! Tests for closing the spinner when a worker thread sends its result.
+
+use std::sync::mpsc;
+
+use egui_modal_spinner::ModalSpinner;
+
+fn update<T>(
+    ctx: &egui::Context,
+    spinner: &mut ModalSpinner,
+    receiver: &mpsc::Receiver<T>,
+) -> Option<T> {
+    let mut output = None;
+    let _ = ctx.run(egui::RawInput::default(), |ctx| {
+        output = spinner.update_with_receiver(ctx, receiver);
+    });
+
+    output
+}
+
+#[test]
+fn spinner_closes_when_value_is_received() {
+    let ctx = egui::Context::default();
+    let mut spinner = ModalSpinner::new();
+    let (tx, rx) = mpsc::channel();
+
+    spinner.open();
+    assert_eq!(update(&ctx, &mut spinner, &rx), None);
+    assert!(spinner.is_open());
+
+    let _ = tx.send(42);
+    assert_eq!(update(&ctx, &mut spinner, &rx), Some(42));
+    assert!(!spinner.is_open());
+}
+
+#[test]
+fn spinner_closes_when_sender_is_dropped() {
+    let ctx = egui::Context::default();
+    let mut spinner = ModalSpinner::new();
+    let (tx, rx) = mpsc::channel::<()>();
+
+    spinner.open();
+    drop(tx);
+
+    assert_eq!(update(&ctx, &mut spinner, &rx), None);
+    assert!(!spinner.is_open());
+}
+
+#[test]
+fn receiver_is_not_polled_while_closed() {
+    let ctx = egui::Context::default();
+    let mut spinner = ModalSpinner::new();
+    let (tx, rx) = mpsc::channel();
+
+    let _ = tx.send(1);
+    assert_eq!(update(&ctx, &mut spinner, &rx), None);
+
+    spinner.open();
+    assert_eq!(update(&ctx, &mut spinner, &rx), Some(1));
+}
+
+#[test]
+fn show_while_returns_result_of_closure() {
+    let ctx = egui::Context::default();
+    let mut spinner = ModalSpinner::new();
+    let (gate_tx, gate_rx) = mpsc::channel::<()>();
+
+    let rx = spinner.show_while(&ctx, move || {
+        let _ = gate_rx.recv();
+        "done"
+    });
+    assert!(spinner.is_open());
+    assert_eq!(update(&ctx, &mut spinner, &rx), None);
+
+    let _ = gate_tx.send(());
+    let result = rx.recv_timeout(std::time::Duration::from_secs(5));
+    assert_eq!(result, Ok("done"));
+}
+
+#[test]
+fn show_while_closes_spinner_when_finished() {
+    let ctx = egui::Context::default();
+    let mut spinner = ModalSpinner::new();
+
+    let rx = spinner.show_while(&ctx, || 1 + 1);
+
+    let mut result = None;
+    while result.is_none() && spinner.is_open() {
+        result = update(&ctx, &mut spinner, &rx);
+    }
+
+    assert_eq!(result, Some(2));
+    assert!(!spinner.is_open());
+}
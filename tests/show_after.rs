@@ -0,0 +1,56 @@
+//! Tests that the modal is only painted once the spinner was open for the show delay.
+
+use std::time::Duration;
+
+use egui_modal_spinner::ModalSpinner;
+
+mod common;
+
+/// Runs a frame at the given time and returns how many shapes were painted.
+fn run(ctx: &egui::Context, spinner: &mut ModalSpinner, time: f64) -> usize {
+    let input = egui::RawInput {
+        time: Some(time),
+        ..common::screen_input(Vec::new())
+    };
+
+    ctx.run(input, |ctx| spinner.update(ctx)).shapes.len()
+}
+
+#[test]
+fn modal_is_painted_after_delay() {
+    let ctx = egui::Context::default();
+    let mut spinner = ModalSpinner::new()
+        .fade_in(false)
+        .show_after(Duration::from_millis(50));
+
+    spinner.open();
+    assert_eq!(run(&ctx, &mut spinner, 0.0), 0);
+    assert_eq!(run(&ctx, &mut spinner, 0.04), 0);
+    assert!(spinner.should_disable_ui());
+
+    assert!(run(&ctx, &mut spinner, 0.06) > 0);
+}
+
+#[test]
+fn spinner_closed_before_delay_is_not_painted() {
+    let ctx = egui::Context::default();
+    let mut spinner = ModalSpinner::new().show_after(Duration::from_millis(50));
+
+    spinner.open();
+    assert_eq!(run(&ctx, &mut spinner, 0.0), 0);
+
+    spinner.close();
+    assert_eq!(run(&ctx, &mut spinner, 0.02), 0);
+    assert!(!spinner.should_disable_ui());
+}
+
+#[test]
+fn maximum_delay_is_never_painted() {
+    let ctx = egui::Context::default();
+    let mut spinner = ModalSpinner::new().show_after(Duration::MAX);
+
+    spinner.open();
+    assert_eq!(run(&ctx, &mut spinner, 0.0), 0);
+    assert_eq!(run(&ctx, &mut spinner, 1.0), 0);
+    assert!(spinner.should_disable_ui());
+}
@@ -0,0 +1,402 @@
+//! Tests for the state handling of the spinner.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use egui_modal_spinner::{ElapsedFormat, ModalSpinner, SpinnerCtx, SpinnerPhase, SpinnerState};
+
+mod common;
+
+use common::Harness;
+
+#[test]
+fn open_and_close() {
+    let mut spinner = ModalSpinner::new();
+    assert_eq!(spinner.state(), &SpinnerState::Closed);
+
+    spinner.open();
+    assert_eq!(spinner.state(), &SpinnerState::Open);
+
+    spinner.close();
+    assert_eq!(spinner.state(), &SpinnerState::Closed);
+}
+
+#[test]
+fn describe_contains_state_and_elapsed_time() {
+    let mut spinner = ModalSpinner::new();
+    assert_eq!(spinner.describe(), "state: Closed\nelapsed: 0");
+
+    spinner.open();
+    assert_eq!(spinner.describe(), "state: Open\nelapsed: 0");
+
+    spinner.set_message("Loading cats...");
+    assert_eq!(
+        spinner.describe(),
+        "state: Open\nelapsed: 0\nmessage: Loading cats..."
+    );
+
+    spinner.clear_message();
+
+    spinner.close();
+    assert_eq!(spinner.describe(), "state: Closed (fading out)\nelapsed: 0");
+}
+
+#[test]
+fn spinner_is_stale_if_not_updated() {
+    let ctx = egui::Context::default();
+    let mut spinner = ModalSpinner::new().stale_threshold(Duration::from_millis(10));
+    assert!(!spinner.is_stale());
+
+    spinner.open();
+    assert!(!spinner.is_stale());
+
+    thread::sleep(Duration::from_millis(20));
+    assert!(spinner.is_stale());
+
+    let _ = ctx.run(egui::RawInput::default(), |ctx| spinner.update(ctx));
+    assert!(!spinner.is_stale());
+
+    spinner.close();
+    thread::sleep(Duration::from_millis(20));
+    assert!(!spinner.is_stale());
+}
+
+#[test]
+fn ui_is_disabled_while_open_and_fading_out() {
+    let mut spinner = ModalSpinner::new();
+    assert!(!spinner.is_open());
+    assert!(!spinner.should_disable_ui());
+
+    spinner.open();
+    assert!(spinner.is_open());
+    assert!(spinner.should_disable_ui());
+
+    spinner.close();
+    assert!(!spinner.is_open());
+    assert!(spinner.should_disable_ui());
+}
+
+#[test]
+fn ui_is_enabled_after_close_without_fade_out() {
+    let mut spinner = ModalSpinner::new().fade_out(false);
+
+    spinner.open();
+    spinner.close();
+
+    assert!(!spinner.should_disable_ui());
+}
+
+#[test]
+fn progress_is_clamped() {
+    let mut spinner = ModalSpinner::new();
+    assert_eq!(spinner.progress(), None);
+
+    spinner.set_progress(0.5);
+    assert_eq!(spinner.progress(), Some(0.5));
+
+    spinner.set_progress(1.5);
+    assert_eq!(spinner.progress(), Some(1.0));
+
+    spinner.set_progress(-1.0);
+    assert_eq!(spinner.progress(), Some(0.0));
+
+    spinner.set_progress(f32::NAN);
+    assert_eq!(spinner.progress(), Some(0.0));
+
+    spinner.clear_progress();
+    assert_eq!(spinner.progress(), None);
+}
+
+#[test]
+fn progress_is_reset_when_opening() {
+    let mut spinner = ModalSpinner::new();
+
+    spinner.open();
+    spinner.set_progress(0.42);
+    assert_eq!(
+        spinner.describe(),
+        "state: Open\nelapsed: 0\nprogress: 42 %"
+    );
+
+    spinner.close();
+    spinner.open();
+    assert_eq!(spinner.progress(), None);
+}
+
+#[test]
+fn content_receives_spinner_ctx() {
+    let ctx = egui::Context::default();
+    let mut spinner = ModalSpinner::new().fade_in(false);
+    let mut spinner_ctx: Option<SpinnerCtx> = None;
+
+    let mut update = |spinner: &mut ModalSpinner| {
+        let _ = ctx.run(egui::RawInput::default(), |ctx| {
+            spinner.update_with_spinner_ctx(ctx, |_, state| spinner_ctx = Some(state.clone()));
+        });
+
+        spinner_ctx.take()
+    };
+
+    assert!(update(&mut spinner).is_none());
+
+    spinner.open();
+    spinner.set_progress(0.5);
+    let state = update(&mut spinner);
+    assert_eq!(state.as_ref().map(|s| s.phase), Some(SpinnerPhase::Open));
+    assert_eq!(state.as_ref().and_then(|s| s.progress), Some(0.5));
+    assert_eq!(state.map(|s| s.cancel_requested), Some(false));
+
+    spinner.close();
+    let state = update(&mut spinner);
+    assert_eq!(state.map(|s| s.phase), Some(SpinnerPhase::FadingOut));
+}
+
+#[test]
+fn spinner_stays_open_for_min_display_time() {
+    let ctx = egui::Context::default();
+    let mut spinner = ModalSpinner::new().min_display_time(Duration::from_millis(50));
+
+    spinner.open();
+    spinner.close();
+    assert!(spinner.is_open());
+
+    let _ = ctx.run(egui::RawInput::default(), |ctx| spinner.update(ctx));
+    assert!(spinner.is_open());
+
+    thread::sleep(Duration::from_millis(60));
+    let _ = ctx.run(egui::RawInput::default(), |ctx| spinner.update(ctx));
+    assert!(!spinner.is_open());
+}
+
+#[test]
+fn spinner_closes_immediately_after_min_display_time() {
+    let mut spinner = ModalSpinner::new().min_display_time(Duration::from_millis(10));
+
+    spinner.open();
+    thread::sleep(Duration::from_millis(20));
+    spinner.close();
+
+    assert!(!spinner.is_open());
+}
+
+#[test]
+fn reopening_cancels_pending_close() {
+    let ctx = egui::Context::default();
+    let mut spinner = ModalSpinner::new().min_display_time(Duration::from_millis(10));
+
+    spinner.open();
+    spinner.close();
+    spinner.open();
+
+    thread::sleep(Duration::from_millis(20));
+    let _ = ctx.run(egui::RawInput::default(), |ctx| spinner.update(ctx));
+
+    assert!(spinner.is_open());
+}
+
+#[test]
+fn reached_milestones_are_reported_once() {
+    let ctx = egui::Context::default();
+    let mut spinner = ModalSpinner::new().milestones([
+        Duration::from_secs(30),
+        Duration::from_millis(20),
+        Duration::ZERO,
+    ]);
+
+    let _ = ctx.run(egui::RawInput::default(), |ctx| spinner.update(ctx));
+    assert!(spinner.take_reached_milestones().is_empty());
+
+    spinner.open();
+    let _ = ctx.run(egui::RawInput::default(), |ctx| spinner.update(ctx));
+    assert_eq!(spinner.take_reached_milestones(), vec![Duration::ZERO]);
+
+    thread::sleep(Duration::from_millis(30));
+    let _ = ctx.run(egui::RawInput::default(), |ctx| spinner.update(ctx));
+    let _ = ctx.run(egui::RawInput::default(), |ctx| spinner.update(ctx));
+    assert_eq!(
+        spinner.take_reached_milestones(),
+        vec![Duration::from_millis(20)]
+    );
+
+    spinner.close();
+    spinner.open();
+    let _ = ctx.run(egui::RawInput::default(), |ctx| spinner.update(ctx));
+    assert_eq!(spinner.take_reached_milestones(), vec![Duration::ZERO]);
+}
+
+#[test]
+fn eta_is_estimated_from_progress_rate() {
+    let ctx = egui::Context::default();
+    let mut spinner = ModalSpinner::new().show_eta(true);
+
+    let run = |spinner: &mut ModalSpinner, time: f64, progress: f32| {
+        spinner.set_progress(progress);
+
+        let input = egui::RawInput {
+            time: Some(time),
+            ..Default::default()
+        };
+        let _ = ctx.run(input, |ctx| spinner.update(ctx));
+    };
+
+    spinner.open();
+    run(&mut spinner, 0.0, 0.0);
+    assert_eq!(spinner.eta(), None);
+
+    // The progress increases by 0.1 per second.
+    run(&mut spinner, 1.0, 0.1);
+    run(&mut spinner, 1.5, 0.1);
+    run(&mut spinner, 2.0, 0.2);
+
+    let eta = spinner.eta().map(|eta| eta.as_secs_f32());
+    assert!(eta.is_some_and(|eta| (eta - 8.0).abs() < 0.01));
+
+    spinner.close();
+    spinner.open();
+    assert_eq!(spinner.eta(), None);
+}
+
+#[test]
+fn default_id_is_derived_from_creation_location() {
+    let first = ModalSpinner::new();
+    let second = ModalSpinner::new();
+    assert_ne!(first.effective_id(), second.effective_id());
+
+    let ids: Vec<_> = (0..2).map(|_| ModalSpinner::new().effective_id()).collect();
+    assert_eq!(ids.first(), ids.last());
+
+    let spinner = ModalSpinner::new().id("custom").order(egui::Order::Tooltip);
+    assert_eq!(spinner.effective_id(), egui::Id::new("custom"));
+    assert_eq!(
+        spinner.layer_id(),
+        egui::LayerId::new(egui::Order::Tooltip, egui::Id::new("custom"))
+    );
+}
+
+#[test]
+fn elapsed_time_formatter_replaces_label() {
+    let harness = Harness::new(
+        ModalSpinner::new()
+            .fade_in(false)
+            .elapsed_label("{} seconds")
+            .elapsed_time_formatter(|elapsed| format!("{} Sekunden", elapsed.as_secs())),
+    );
+
+    assert!(harness.text_rect("0 Sekunden").is_some());
+    assert!(harness.text_rect("0 seconds").is_none());
+}
+
+#[test]
+fn elapsed_time_is_formatted() {
+    let elapsed = Duration::from_millis(3_723_456);
+
+    assert_eq!(ElapsedFormat::Seconds.format(elapsed), "3723");
+    assert_eq!(ElapsedFormat::MinutesSeconds.format(elapsed), "62:03");
+    assert_eq!(
+        ElapsedFormat::HoursMinutesSeconds.format(elapsed),
+        "01:02:03"
+    );
+    assert_eq!(ElapsedFormat::Milliseconds.format(elapsed), "3723456");
+
+    let spinner = ModalSpinner::new().elapsed_format(ElapsedFormat::MinutesSeconds);
+    assert_eq!(spinner.describe(), "state: Closed\nelapsed: 00:00");
+}
+
+#[test]
+fn spinner_is_closed_after_timeout() {
+    let ctx = egui::Context::default();
+    let mut spinner = ModalSpinner::new().timeout(Duration::from_millis(10));
+
+    spinner.open();
+    assert!(!spinner.timed_out());
+
+    thread::sleep(Duration::from_millis(20));
+    assert!(spinner.is_open());
+
+    let _ = ctx.run(egui::RawInput::default(), |ctx| spinner.update(ctx));
+    assert!(!spinner.is_open());
+    assert!(spinner.timed_out());
+
+    spinner.open();
+    assert!(!spinner.timed_out());
+}
+
+#[test]
+fn lifecycle_callbacks_wait_for_fade_out() {
+    let ctx = egui::Context::default();
+    let opened = Arc::new(AtomicUsize::new(0));
+    let closed = Arc::new(AtomicUsize::new(0));
+
+    let mut spinner = ModalSpinner::new()
+        .fade_in(false)
+        .on_open({
+            let opened = Arc::clone(&opened);
+            move || {
+                opened.fetch_add(1, Ordering::Relaxed);
+            }
+        })
+        .on_close({
+            let closed = Arc::clone(&closed);
+            move || {
+                closed.fetch_add(1, Ordering::Relaxed);
+            }
+        });
+
+    let run = |spinner: &mut ModalSpinner, time: f64| {
+        let input = egui::RawInput {
+            time: Some(time),
+            ..Default::default()
+        };
+        let _ = ctx.run(input, |ctx| spinner.update(ctx));
+    };
+
+    spinner.open();
+    assert_eq!(opened.load(Ordering::Relaxed), 1);
+    run(&mut spinner, 0.0);
+
+    spinner.close();
+    run(&mut spinner, 0.01);
+    assert_eq!(closed.load(Ordering::Relaxed), 0);
+
+    // Opening again while fading out does not call the open callback again.
+    spinner.open();
+    spinner.close();
+    assert_eq!(opened.load(Ordering::Relaxed), 1);
+
+    run(&mut spinner, 1.0);
+    run(&mut spinner, 1.1);
+    assert_eq!(closed.load(Ordering::Relaxed), 1);
+
+    spinner.open();
+    assert_eq!(opened.load(Ordering::Relaxed), 2);
+}
+
+#[test]
+fn spinner_stays_open_while_a_source_is_active() {
+    let mut spinner = ModalSpinner::new();
+
+    spinner.open_for("db-sync");
+    spinner.set_progress(0.5);
+    spinner.open_for("upload");
+    spinner.open_for("upload");
+    assert_eq!(spinner.progress(), Some(0.5));
+    assert_eq!(
+        spinner.active_sources().collect::<Vec<_>>(),
+        vec!["db-sync", "upload"]
+    );
+
+    spinner.close_for("upload");
+    spinner.close_for("unknown");
+    assert!(spinner.is_open());
+
+    spinner.close_for("db-sync");
+    assert!(!spinner.is_open());
+
+    // Closing without a source discards all sources.
+    spinner.open_for("db-sync");
+    spinner.close();
+    assert!(!spinner.is_open());
+    assert_eq!(spinner.active_sources().count(), 0);
+}
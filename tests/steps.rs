@@ -0,0 +1,60 @@
+//! Tests for the list of steps displayed beneath the spinner.
+
+use egui_modal_spinner::{ModalSpinner, StepState};
+
+#[test]
+fn first_uncompleted_step_is_active() {
+    let mut spinner = ModalSpinner::new();
+    spinner.set_steps(["Download", "Verify", "Install"]);
+
+    assert_eq!(spinner.step_state(0), Some(StepState::Active));
+    assert_eq!(spinner.step_state(1), Some(StepState::Pending));
+
+    spinner.complete_step(0);
+    spinner.complete_step(5);
+    assert_eq!(spinner.step_state(0), Some(StepState::Completed));
+    assert_eq!(spinner.step_state(1), Some(StepState::Active));
+    assert_eq!(spinner.step_state(2), Some(StepState::Pending));
+    assert_eq!(spinner.step_state(3), None);
+
+    spinner.clear_steps();
+    assert_eq!(spinner.step_state(0), None);
+}
+
+#[test]
+fn steps_are_rendered_beneath_spinner() {
+    let ctx = egui::Context::default();
+    let mut spinner = ModalSpinner::new().fade_in(false).show_elapsed_time(false);
+
+    spinner.open();
+    spinner.set_steps(["Download", "Install"]);
+    spinner.complete_step(0);
+
+    let mut labels = Vec::new();
+
+    // The fonts are only available after the first frame.
+    for _ in 0..2 {
+        let output = ctx.run(egui::RawInput::default(), |ctx| spinner.update(ctx));
+
+        labels = output
+            .shapes
+            .iter()
+            .filter_map(|shape| match &shape.shape {
+                egui::Shape::Text(text) => Some((text.galley.text().to_owned(), text.pos)),
+                _ => None,
+            })
+            .collect();
+    }
+
+    let screen_center = ctx.screen_rect().center();
+
+    assert_eq!(labels.len(), 2);
+    assert!(labels.first().map(|(_, pos)| pos.y) < labels.last().map(|(_, pos)| pos.y));
+    assert!(labels
+        .iter()
+        .all(|(_, pos)| (pos.x - screen_center.x).abs() < 100.0));
+    assert_eq!(
+        labels.first().map(|(text, _)| text.as_str()),
+        Some("Download")
+    );
+}
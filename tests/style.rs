@@ -0,0 +1,216 @@
+//! Tests for the visual configuration of the overlay.
+
+use egui_modal_spinner::{ModalSpinner, OverlayStyle, SpinnerStyle};
+
+mod common;
+
+#[test]
+fn builder_methods_update_style() {
+    let spinner = ModalSpinner::new()
+        .fill_color(egui::Color32::BLUE)
+        .rounding(8.0)
+        .spinner_size(40.0)
+        .spinner_color(egui::Color32::RED);
+
+    let expected = OverlayStyle {
+        fill_color: Some(egui::Color32::BLUE),
+        rounding: egui::Rounding::same(8.0),
+        spinner_size: Some(40.0),
+        spinner_color: Some(egui::Color32::RED),
+        ..Default::default()
+    };
+
+    assert_eq!(spinner.style(), &expected);
+}
+
+#[test]
+fn style_can_be_swapped_at_runtime() {
+    let mut spinner = ModalSpinner::new().overlay_style(OverlayStyle {
+        spinner_size: Some(20.0),
+        ..Default::default()
+    });
+
+    spinner.style_mut().text_color = Some(egui::Color32::GREEN);
+    assert_eq!(spinner.style().spinner_size, Some(20.0));
+    assert_eq!(spinner.style().text_color, Some(egui::Color32::GREEN));
+
+    spinner.set_style(OverlayStyle::default());
+    assert_eq!(spinner.style(), &OverlayStyle::default());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn style_can_be_serialized() {
+    let style = OverlayStyle {
+        fill_color: Some(egui::Color32::BLUE),
+        text_style: egui::TextStyle::Monospace,
+        ..Default::default()
+    };
+
+    let serialized = ron::to_string(&style).unwrap_or_default();
+    let deserialized: Result<OverlayStyle, _> = ron::from_str(&serialized);
+
+    assert_eq!(deserialized.ok(), Some(style));
+}
+
+#[cfg(feature = "debug-tools")]
+#[test]
+fn style_editor_is_unchanged_without_input() {
+    let ctx = egui::Context::default();
+    let mut style = OverlayStyle::default();
+    let mut changed = true;
+
+    let _ = ctx.run(egui::RawInput::default(), |ctx| {
+        egui::CentralPanel::default().show(ctx, |ui| {
+            changed = egui_modal_spinner::style_editor_ui(&mut style, ui);
+        });
+    });
+
+    assert!(!changed);
+    assert_eq!(style, OverlayStyle::default());
+}
+
+#[test]
+fn long_labels_are_truncated() {
+    let ctx = egui::Context::default();
+    let mut spinner = ModalSpinner::new()
+        .fade_in(false)
+        .elapsed_label("A very long label ".repeat(50))
+        .overlay_style(OverlayStyle {
+            max_label_width: Some(100.0),
+            ..Default::default()
+        });
+
+    spinner.open();
+
+    let input = common::screen_input(Vec::new());
+
+    let mut label_width = None;
+
+    // The fonts are only available after the first frame.
+    for _ in 0..2 {
+        let output = ctx.run(input.clone(), |ctx| spinner.update(ctx));
+
+        label_width = output.shapes.iter().find_map(|shape| match &shape.shape {
+            egui::Shape::Text(text) => Some(text.galley.rect.width()),
+            _ => None,
+        });
+    }
+
+    assert!(label_width.is_some_and(|width| width <= 100.0));
+}
+
+#[test]
+fn style_can_be_created_from_egui_style() {
+    let mut egui_style = egui::Style {
+        visuals: egui::Visuals::light(),
+        ..Default::default()
+    };
+    egui_style.spacing.interact_size.y = 30.0;
+
+    let style = OverlayStyle::from(&egui_style);
+
+    assert_eq!(style.spinner_size, Some(30.0));
+    assert_eq!(style.fill_color, Some(egui::Color32::from_white_alpha(40)));
+    assert_eq!(style.text_color, Some(egui_style.visuals.text_color()));
+    assert!(style.spinner_color.is_some());
+    assert_eq!(style.text_style, OverlayStyle::default().text_style);
+}
+
+#[test]
+fn style_from_visuals_matches_defaults() {
+    let style = OverlayStyle::from(&egui::Visuals::dark());
+
+    assert_eq!(
+        style.spinner_color,
+        Some(egui::Visuals::dark().widgets.inactive.fg_stroke.color)
+    );
+    assert_eq!(style.fill_color, Some(egui::Color32::from_black_alpha(120)));
+}
+
+#[test]
+fn spinner_uses_accent_color_from_visuals() {
+    let ctx = egui::Context::default();
+    let accent = egui::Color32::from_rgb(200, 40, 120);
+    ctx.style_mut(|style| style.visuals.selection.bg_fill = accent);
+
+    let mut spinner = ModalSpinner::new().fade_in(false).accent_from_visuals(true);
+    spinner.open();
+
+    let output = ctx.run(egui::RawInput::default(), |ctx| spinner.update(ctx));
+
+    let uses_accent = output.shapes.iter().any(|shape| match &shape.shape {
+        egui::Shape::Path(path) => path.stroke.color == egui::epaint::ColorMode::Solid(accent),
+        _ => false,
+    });
+    assert!(uses_accent);
+}
+
+#[test]
+fn item_spacing_changes_gap_between_elements() {
+    let label_gap = |item_spacing: f32| {
+        let ctx = egui::Context::default();
+        let mut spinner = ModalSpinner::new()
+            .fade_in(false)
+            .spinner_size(20.0)
+            .item_spacing(item_spacing);
+
+        spinner.open();
+
+        let mut offset = None;
+
+        // The fonts are only available after the first frame.
+        for _ in 0..2 {
+            let output = ctx.run(egui::RawInput::default(), |ctx| spinner.update(ctx));
+
+            let label_top = output.shapes.iter().find_map(|shape| match &shape.shape {
+                egui::Shape::Text(text) => Some(text.pos.y),
+                _ => None,
+            });
+            let spinner_bottom = output.shapes.iter().find_map(|shape| match &shape.shape {
+                egui::Shape::Path(path) => Some(path.visual_bounding_rect().bottom()),
+                _ => None,
+            });
+
+            offset = label_top
+                .zip(spinner_bottom)
+                .map(|(top, bottom)| top - bottom);
+        }
+
+        offset.unwrap_or_default()
+    };
+
+    assert_eq!(
+        ModalSpinner::new().item_spacing(-1.0).style().item_spacing,
+        None
+    );
+
+    let tight = label_gap(0.0);
+    let loose = label_gap(20.0);
+
+    assert!(loose > tight + 20.0);
+}
+
+#[test]
+fn spinner_styles_are_painted_in_spinner_color() {
+    let ctx = egui::Context::default();
+    let color = egui::Color32::from_rgb(200, 40, 120);
+
+    for spinner_style in SpinnerStyle::ALL {
+        let mut spinner = ModalSpinner::new()
+            .fade_in(false)
+            .spinner_color(color)
+            .spinner_style(spinner_style);
+        spinner.open();
+
+        let output = ctx.run(egui::RawInput::default(), |ctx| spinner.update(ctx));
+
+        let painted = output.shapes.iter().any(|shape| match &shape.shape {
+            egui::Shape::Path(path) => path.stroke.color == egui::epaint::ColorMode::Solid(color),
+            egui::Shape::Circle(circle) => circle.fill == color,
+            egui::Shape::Rect(rect) => rect.fill == color,
+            _ => false,
+        });
+        assert!(painted, "{spinner_style:?} is not painted");
+    }
+}
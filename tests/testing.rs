@@ -0,0 +1,52 @@
+//! Tests for the simulated clock enabled with the testing feature.
+#![cfg(feature = "testing")]
+
+use std::time::Duration;
+
+use egui_modal_spinner::ModalSpinner;
+
+#[test]
+fn min_display_time_is_simulated() {
+    let mut spinner = ModalSpinner::new().min_display_time(Duration::from_secs(2));
+
+    spinner.open();
+    spinner.advance_time(Duration::from_secs(1));
+    spinner.close();
+    assert!(spinner.is_open());
+
+    spinner.advance_time(Duration::from_secs(1));
+    assert!(!spinner.is_open());
+    assert!(spinner.elapsed() >= Duration::from_secs(2));
+}
+
+#[test]
+fn fade_animation_is_simulated() {
+    let mut spinner = ModalSpinner::new();
+    assert!(spinner.opacity() <= 0.0);
+
+    spinner.open();
+    spinner.advance_time(Duration::ZERO);
+    let fading_in = spinner.opacity();
+    assert!(fading_in > 0.0 && fading_in < 1.0);
+
+    spinner.advance_time(Duration::from_secs(1));
+    assert!((spinner.opacity() - 1.0).abs() < f32::EPSILON);
+
+    spinner.close();
+    spinner.advance_time(Duration::from_millis(10));
+    assert!(spinner.opacity() > 0.0 && spinner.should_disable_ui());
+
+    spinner.advance_time(Duration::from_secs(1));
+    assert!(spinner.opacity() <= 0.0);
+    assert!(!spinner.should_disable_ui());
+}
+
+#[test]
+fn advancing_by_maximum_duration_does_not_panic() {
+    let mut spinner = ModalSpinner::new();
+
+    spinner.open();
+    spinner.advance_time(Duration::MAX);
+    spinner.advance_time(Duration::MAX);
+    let _ = spinner.elapsed();
+}
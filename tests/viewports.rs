@@ -0,0 +1,58 @@
+//! Tests for displaying the modal inside secondary viewports.
+
+use egui_modal_spinner::{ModalSpinner, ViewportCoverage};
+
+/// Runs a single frame in the given viewport and returns the number of painted shapes.
+fn run_frame(
+    ctx: &egui::Context,
+    viewport_id: egui::ViewportId,
+    run_ui: impl FnMut(&egui::Context),
+) -> usize {
+    let mut input = egui::RawInput {
+        viewport_id,
+        ..Default::default()
+    };
+    input
+        .viewports
+        .insert(viewport_id, egui::ViewportInfo::default());
+
+    ctx.run(input, run_ui).shapes.len()
+}
+
+#[test]
+fn secondary_viewport_only_displays_background() {
+    let ctx = egui::Context::default();
+    let secondary = egui::ViewportId::from_hash_of("secondary");
+    let mut spinner = ModalSpinner::new()
+        .fade_in(false)
+        .secondary_viewports(ViewportCoverage::DimOnly);
+
+    spinner.open();
+    let root_shapes = run_frame(&ctx, egui::ViewportId::ROOT, |ctx| spinner.update(ctx));
+    let secondary_shapes = run_frame(&ctx, secondary, |ctx| spinner.update(ctx));
+
+    assert!(root_shapes > 1);
+    assert_eq!(secondary_shapes, 1);
+
+    // Closing the spinner is only applied from the root viewport.
+    spinner.handle().close();
+    let _ = run_frame(&ctx, secondary, |ctx| spinner.update(ctx));
+    assert!(spinner.is_open());
+
+    let _ = run_frame(&ctx, egui::ViewportId::ROOT, |ctx| spinner.update(ctx));
+    assert!(!spinner.is_open());
+}
+
+#[test]
+fn secondary_viewport_can_be_disabled() {
+    let ctx = egui::Context::default();
+    let secondary = egui::ViewportId::from_hash_of("secondary");
+    let mut spinner = ModalSpinner::new()
+        .fade_in(false)
+        .secondary_viewports(ViewportCoverage::Disabled);
+
+    spinner.open();
+    let _ = run_frame(&ctx, egui::ViewportId::ROOT, |ctx| spinner.update(ctx));
+
+    assert_eq!(run_frame(&ctx, secondary, |ctx| spinner.update(ctx)), 0);
+}
@@ -0,0 +1,74 @@
+//! Tests that the area covered by the modal is reported every frame.
+
+use std::sync::{Arc, Mutex};
+
+use egui_modal_spinner::{ModalSpinner, OverlayVisibility};
+
+const SCREEN_RECT: egui::Rect =
+    egui::Rect::from_min_max(egui::Pos2::ZERO, egui::pos2(800.0, 600.0));
+
+fn run(ctx: &egui::Context, spinner: &mut ModalSpinner) {
+    let input = egui::RawInput {
+        screen_rect: Some(SCREEN_RECT),
+        ..Default::default()
+    };
+
+    let _ = ctx.run(input, |ctx| spinner.update(ctx));
+}
+
+fn recording_spinner() -> (ModalSpinner, Arc<Mutex<Vec<OverlayVisibility>>>) {
+    let reports = Arc::new(Mutex::new(Vec::new()));
+    let reports_clone = Arc::clone(&reports);
+
+    let spinner = ModalSpinner::new().on_visibility(move |visibility| {
+        if let Ok(mut reports) = reports_clone.lock() {
+            reports.push(*visibility);
+        }
+    });
+
+    (spinner, reports)
+}
+
+fn take(reports: &Mutex<Vec<OverlayVisibility>>) -> Vec<OverlayVisibility> {
+    reports
+        .lock()
+        .map(|mut r| r.drain(..).collect())
+        .unwrap_or_default()
+}
+
+#[test]
+fn visibility_is_not_reported_while_closed() {
+    let ctx = egui::Context::default();
+    let (mut spinner, reports) = recording_spinner();
+
+    run(&ctx, &mut spinner);
+
+    assert!(take(&reports).is_empty());
+}
+
+#[test]
+fn visibility_is_reported_while_open_and_fading_out() {
+    let ctx = egui::Context::default();
+    let (mut spinner, reports) = recording_spinner();
+
+    spinner.open();
+    for _ in 0..10 {
+        run(&ctx, &mut spinner);
+    }
+
+    let open = take(&reports);
+    assert_eq!(open.len(), 10);
+    assert!(open.iter().all(|v| v.rect == SCREEN_RECT));
+    assert!(open.last().is_some_and(|v| v.opacity >= 1.0));
+
+    spinner.close();
+    while spinner.should_disable_ui() {
+        run(&ctx, &mut spinner);
+    }
+
+    let closing = take(&reports);
+    assert!(closing.last().is_some_and(|v| v.opacity <= 0.0));
+
+    run(&ctx, &mut spinner);
+    assert!(take(&reports).is_empty());
+}